@@ -0,0 +1,52 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use bc_hash::sha3::{Sha3_256, Shake128};
+use bc_hash::{FinishXOF, OneWayHasher};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+/// Absorbs a buffer into SHA3-256, next to the RustCrypto implementation for reference.
+fn absorb(c: &mut Criterion) {
+    let data: Vec<u8> = vec![0xa5; 1 << 20];
+    let mut group = c.benchmark_group("sha3-256 1MiB");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("bc_hash", |b| {
+        let mut digest: [u8; 32] = [0; 32];
+        let mut ctx: Sha3_256 = Sha3_256::init();
+        b.iter(|| {
+            ctx.reset().update(black_box(&data)).finish_into(&mut digest);
+            digest
+        })
+    });
+    group.bench_function("sha3 crate", |b| {
+        use sha3::Digest;
+        b.iter(|| sha3::Sha3_256::digest(black_box(&data)))
+    });
+    group.finish();
+}
+
+/// Squeezes a buffer full of SHAKE128 output.
+fn squeeze(c: &mut Criterion) {
+    let mut output: Vec<u8> = vec![0; 1 << 20];
+    let mut group = c.benchmark_group("shake128 squeeze 1MiB");
+    group.throughput(Throughput::Bytes(output.len() as u64));
+    group.bench_function("bc_hash", |b| {
+        let mut ctx: Shake128<32> = Shake128::init();
+        b.iter(|| {
+            ctx.reset().update(b"seed").finish_xof(black_box(&mut output));
+        })
+    });
+    group.bench_function("sha3 crate", |b| {
+        use sha3::digest::{ExtendableOutput, Update, XofReader};
+        b.iter(|| {
+            let mut ctx = sha3::Shake128::default();
+            ctx.update(b"seed");
+            ctx.finalize_xof().read(black_box(&mut output));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, absorb, squeeze);
+criterion_main!(benches);
@@ -0,0 +1,153 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use bc_hash::merkle::compute_root;
+use bc_hash::multibuffer::{sha256_many, sha256_many_lanes};
+use bc_hash::sha2::{compress256, compress256_portable, Sha256, Sha256d, Sha512};
+use bc_hash::OneWayHasher;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+const INITIAL_VALUES_256: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Compares the SHA extensions (when the CPU has them) to the portable compression function.
+fn compression(c: &mut Criterion) {
+    let chunks: Vec<[u8; 64]> = vec![[0xa5; 64]; 16384];
+    let mut group = c.benchmark_group("sha256 compression 1MiB");
+    group.throughput(Throughput::Bytes(16384 * 64));
+    group.bench_function("dispatched", |b| {
+        b.iter(|| {
+            let mut state: [u32; 8] = INITIAL_VALUES_256;
+            compress256(&mut state, black_box(&chunks));
+            state
+        })
+    });
+    group.bench_function("portable", |b| {
+        b.iter(|| {
+            let mut state: [u32; 8] = INITIAL_VALUES_256;
+            compress256_portable(&mut state, black_box(&chunks));
+            state
+        })
+    });
+    group.finish();
+}
+
+/// Hashes a buffer through the full update/finish interface.
+fn hashing(c: &mut Criterion) {
+    let data: Vec<u8> = vec![0xa5; 1 << 20];
+    let mut group = c.benchmark_group("sha256 update 1MiB");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("Sha256", |b| {
+        let mut digest: [u8; 32] = [0; 32];
+        let mut ctx: Sha256 = Sha256::init();
+        b.iter(|| {
+            ctx.reset().update(black_box(&data)).finish_into(&mut digest);
+            digest
+        })
+    });
+    group.finish();
+}
+
+/// Returns the padding that ```finish()``` used to build in a ```Vec``` and absorb with ```update()```
+/// for a message of ```len``` bytes, before it padded the message schedule in place.
+fn allocating_padding(len: usize, chunk_len: usize, len_size: usize) -> Vec<u8> {
+    let mut buf: Vec<u8> = Vec::new();
+    buf.push(128u8);
+    while (buf.len() + len % chunk_len + len_size) % chunk_len != 0 {
+        buf.push(0u8);
+    }
+    buf.extend_from_slice(&((len * 8) as u64).to_be_bytes()[(8 - len_size)..]);
+    buf
+}
+
+/// Hashes a single 64 byte message, which is dominated by the cost of padding and finalization.
+/// The "allocating padding" functions repeat the old ```finish()``` through the public interface,
+/// reading the final state with ```midstate()```, as a baseline for the in place padding.
+fn finish(c: &mut Criterion) {
+    let data: [u8; 64] = [0xa5; 64];
+    let mut group = c.benchmark_group("finish 64B");
+    group.bench_function("Sha256", |b| {
+        let mut digest: [u8; 32] = [0; 32];
+        let mut ctx: Sha256 = Sha256::init();
+        b.iter(|| {
+            ctx.reset().update(black_box(&data)).finish_into(&mut digest);
+            digest
+        })
+    });
+    group.bench_function("Sha256 allocating padding", |b| {
+        let mut digest: [u8; 32] = [0; 32];
+        let mut ctx: Sha256 = Sha256::init();
+        b.iter(|| {
+            let padding: Vec<u8> = allocating_padding(data.len(), 64, 4);
+            let state: [u32; 8] = ctx.reset().update(black_box(&data)).update(&padding).midstate().unwrap().state;
+            for (w, s) in digest.chunks_exact_mut(4).zip(state) {
+                w.copy_from_slice(&s.to_be_bytes());
+            }
+            digest
+        });
+        assert!(digest == Sha256::digest(data).0, "The allocating padding is wrong");
+    });
+    group.bench_function("Sha512", |b| {
+        let mut digest: [u8; 64] = [0; 64];
+        let mut ctx: Sha512 = Sha512::init();
+        b.iter(|| {
+            ctx.reset().update(black_box(&data)).finish_into(&mut digest);
+            digest
+        })
+    });
+    group.bench_function("Sha512 allocating padding", |b| {
+        let mut digest: [u8; 64] = [0; 64];
+        let mut ctx: Sha512 = Sha512::init();
+        b.iter(|| {
+            let padding: Vec<u8> = allocating_padding(data.len(), 128, 8);
+            let state: [u64; 8] = ctx.reset().update(black_box(&data)).update(&padding).midstate().unwrap().state;
+            for (w, s) in digest.chunks_exact_mut(8).zip(state) {
+                w.copy_from_slice(&s.to_be_bytes());
+            }
+            digest
+        });
+        assert!(digest == Sha512::digest(data).0, "The allocating padding is wrong");
+    });
+    group.finish();
+}
+
+/// Compares hashing many 64 byte messages several at a time to hashing them one after another.
+fn many(c: &mut Criterion) {
+    let messages: Vec<[u8; 64]> = vec![[0xa5; 64]; 4096];
+    let mut digests: Vec<[u8; 32]> = vec![[0; 32]; 4096];
+    let mut group = c.benchmark_group("sha256 4096 x 64B");
+    group.throughput(Throughput::Bytes(4096 * 64));
+    group.bench_function("sha256_many", |b| {
+        b.iter(|| sha256_many(black_box(&messages), &mut digests).unwrap())
+    });
+    // each lane count this CPU supports, where 1 is SHA-NI when the CPU has it
+    for lanes in [1, 4, 8] {
+        if sha256_many_lanes(lanes, &messages[..lanes], &mut digests[..lanes]).is_ok() {
+            group.bench_function(format!("{} lanes", lanes), |b| {
+                b.iter(|| sha256_many_lanes(lanes, black_box(&messages), &mut digests).unwrap())
+            });
+        }
+    }
+    group.bench_function("sequential", |b| {
+        let mut ctx: Sha256 = Sha256::init();
+        b.iter(|| {
+            for (m, d) in black_box(&messages).iter().zip(digests.iter_mut()) {
+                ctx.reset().update(m).finish_into(d);
+            }
+        })
+    });
+    group.finish();
+
+    let leaves: Vec<[u8; 32]> = vec![[0x5a; 32]; 4096];
+    c.bench_function("sha256d merkle root 4096 leaves", |b| {
+        b.iter(|| {
+            let mut nodes: Vec<[u8; 32]> = black_box(&leaves).clone();
+            compute_root::<32, Sha256d>(&mut nodes).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, compression, hashing, finish, many);
+criterion_main!(benches);
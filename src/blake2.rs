@@ -0,0 +1,258 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::OneWayHasher;
+
+// https://www.rfc-editor.org/rfc/rfc7693
+// https://www.blake2.net/blake2.pdf
+
+/// The BLAKE2b initialization vector, which is the same as the SHA-512 initial values.
+const IV_B: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The BLAKE2s initialization vector, which is the same as the SHA-256 initial values.
+const IV_S: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The message word permutations used by each round.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Defines a BLAKE2 context and implements ```OneWayHasher``` for it.
+/// Parameters are as follows:
+///    $name - The name of the struct to define
+///    $prefix - The name of the algorithm, to which the digest length in bits is appended
+///    $typ - The unsigned integer type used for calculations (u32 or u64)
+///    $ctr - The unsigned integer type of the byte counter (u64 or u128)
+///    $iv - The initialization vector
+///    $block_len - The number of bytes in a block
+///    $max_len - The maximum length of the digest and key
+///    $salt_len - The length of the salt and personalization strings
+///    $rounds - The number of rounds performed by the compression function
+///    $r1 to $r4 - Integers used in the rotations performed by the mixing function
+macro_rules! blake2_context {
+    ($name:ident, $prefix:literal, $typ:ty, $ctr:ty, $iv:ident, $block_len:literal, $max_len:literal, $salt_len:literal, $rounds:literal, $r1:literal, $r2:literal, $r3:literal, $r4:literal) => {
+        pub struct $name<const MDLEN: usize> {
+            h: [$typ; 8],
+            h0: [$typ; 8],
+            t: $ctr,
+            buf: [u8; $block_len],
+            buf_num: usize,
+            key: [u8; $block_len],
+            key_len: usize,
+        }
+
+        impl<const MDLEN: usize> $name<MDLEN> {
+            /// Fails to compile if ```MDLEN``` is not a valid digest length.
+            const VALID_MDLEN: () = assert!(MDLEN > 0 && MDLEN <= $max_len, "Invalid BLAKE2 digest length.");
+
+            /// Returns a new context for keyed hashing (MAC mode).
+            pub fn with_key(key: &[u8]) -> Result<Self> {
+                Self::with_params(key, &[], &[])
+            }
+
+            /// Returns a new context using the optional ```key```, ```salt```, and ```personal```
+            /// parameters. The key may be up to 64 bytes long for BLAKE2b and 32 bytes for BLAKE2s,
+            /// while the salt and personalization strings may be up to 16 bytes long for BLAKE2b and
+            /// 8 bytes for BLAKE2s. Shorter salt and personalization strings are padded with zeros.
+            /// Pass an empty slice to omit any of them.
+            pub fn with_params(key: &[u8], salt: &[u8], personal: &[u8]) -> Result<Self> {
+                #[allow(clippy::let_unit_value)]
+                let _ = Self::VALID_MDLEN;
+                if key.len() > $max_len {
+                    Err(Error::new(ErrorKind::SliceTooLong, "The key is longer than the maximum key length."))
+                } else if salt.len() > $salt_len {
+                    Err(Error::new(ErrorKind::SliceTooLong, "The salt is longer than the maximum salt length."))
+                } else if personal.len() > $salt_len {
+                    Err(Error::new(ErrorKind::SliceTooLong, "The personalization string is longer than the maximum length."))
+                } else {
+                    // build the parameter block for sequential mode (fanout = 1, depth = 1)
+                    const WORD: usize = core::mem::size_of::<$typ>();
+                    let mut param: [u8; 8 * WORD] = [0; 8 * WORD];
+                    param[0] = MDLEN as u8;
+                    param[1] = key.len() as u8;
+                    param[2] = 1;
+                    param[3] = 1;
+                    param[(4 * WORD)..(4 * WORD + salt.len())].clone_from_slice(salt);
+                    param[(6 * WORD)..(6 * WORD + personal.len())].clone_from_slice(personal);
+                    let mut h0: [$typ; 8] = $iv;
+                    for (h, p) in h0.iter_mut().zip(param.chunks_exact(WORD)) {
+                        *h ^= <$typ>::from_le_bytes(p.try_into().unwrap());
+                    }
+                    let mut ctx: Self = Self {
+                        h: h0,
+                        h0,
+                        t: 0,
+                        buf: [0; $block_len],
+                        buf_num: 0,
+                        key: [0; $block_len],
+                        key_len: key.len(),
+                    };
+                    ctx.key[..key.len()].clone_from_slice(key);
+                    ctx.reset();
+                    Ok(ctx)
+                }
+            }
+
+            /// Compression function. Processes the block currently held in the buffer.
+            fn compress(&mut self, last: bool) {
+                let mut m: [$typ; 16] = [0; 16];
+                for (w, b) in m.iter_mut().zip(self.buf.chunks_exact(core::mem::size_of::<$typ>())) {
+                    *w = <$typ>::from_le_bytes(b.try_into().unwrap());
+                }
+
+                let mut v: [$typ; 16] = [0; 16];
+                v[..8].copy_from_slice(&self.h);
+                v[8..].copy_from_slice(&$iv);
+                v[12] ^= self.t as $typ;
+                v[13] ^= (self.t >> <$typ>::BITS) as $typ;
+                if last {
+                    v[14] ^= <$typ>::MAX;
+                }
+
+                // the mixing function
+                macro_rules! g {
+                    ($a:literal, $b:literal, $c:literal, $d:literal, $x:expr, $y:expr) => {
+                        v[$a] = v[$a].wrapping_add(v[$b]).wrapping_add($x);
+                        v[$d] = (v[$d] ^ v[$a]).rotate_right($r1);
+                        v[$c] = v[$c].wrapping_add(v[$d]);
+                        v[$b] = (v[$b] ^ v[$c]).rotate_right($r2);
+                        v[$a] = v[$a].wrapping_add(v[$b]).wrapping_add($y);
+                        v[$d] = (v[$d] ^ v[$a]).rotate_right($r3);
+                        v[$c] = v[$c].wrapping_add(v[$d]);
+                        v[$b] = (v[$b] ^ v[$c]).rotate_right($r4);
+                    };
+                }
+
+                for i in 0..$rounds {
+                    let s: &[usize; 16] = &SIGMA[i % 10];
+                    g!(0, 4, 8, 12, m[s[0]], m[s[1]]);
+                    g!(1, 5, 9, 13, m[s[2]], m[s[3]]);
+                    g!(2, 6, 10, 14, m[s[4]], m[s[5]]);
+                    g!(3, 7, 11, 15, m[s[6]], m[s[7]]);
+                    g!(0, 5, 10, 15, m[s[8]], m[s[9]]);
+                    g!(1, 6, 11, 12, m[s[10]], m[s[11]]);
+                    g!(2, 7, 8, 13, m[s[12]], m[s[13]]);
+                    g!(3, 4, 9, 14, m[s[14]], m[s[15]]);
+                }
+
+                for i in 0..8 {
+                    self.h[i] ^= v[i] ^ v[i + 8];
+                }
+            }
+        }
+
+        impl<const MDLEN: usize> OneWayHasher<MDLEN> for $name<MDLEN> {
+            const NAME: &'static str = match MDLEN {
+                20 => concat!($prefix, "-160"),
+                28 => concat!($prefix, "-224"),
+                32 => concat!($prefix, "-256"),
+                48 => concat!($prefix, "-384"),
+                64 => concat!($prefix, "-512"),
+                _ => $prefix,
+            };
+            const BLOCK_SIZE: usize = $block_len;
+
+            #[inline]
+            fn init() -> Self {
+                // an empty key, salt and personalization can never fail
+                Self::with_params(&[], &[], &[]).unwrap()
+            }
+
+            #[inline]
+            fn reset(&mut self) -> &mut Self {
+                self.h = self.h0;
+                self.t = 0;
+                self.buf = [0; $block_len];
+                self.buf_num = 0;
+                if self.key_len > 0 {
+                    // a key is processed as if it were a full first block of message data
+                    self.buf = self.key;
+                    self.buf_num = $block_len;
+                }
+                self
+            }
+
+            #[inline]
+            fn update(&mut self, data: &[u8]) -> &mut Self {
+                let mut bytes_copied: usize = 0;
+                while bytes_copied < data.len() {
+                    // the last block must be compressed by finish(), so only compress a full
+                    // buffer once we know more data is coming
+                    if self.buf_num == $block_len {
+                        self.t = self.t.wrapping_add($block_len);
+                        self.compress(false);
+                        self.buf_num = 0;
+                    }
+                    let len: usize = (data.len() - bytes_copied).min($block_len - self.buf_num);
+                    self.buf[self.buf_num..(self.buf_num + len)]
+                        .clone_from_slice(&data[bytes_copied..(bytes_copied + len)]);
+                    bytes_copied += len;
+                    self.buf_num += len;
+                }
+                self
+            }
+
+            #[inline]
+            fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
+                self.t = self.t.wrapping_add(self.buf_num as $ctr);
+                self.buf[self.buf_num..].fill(0);
+                self.compress(true);
+                for (i, b) in digest.iter_mut().enumerate() {
+                    *b = self.h[i / core::mem::size_of::<$typ>()].to_le_bytes()[i % core::mem::size_of::<$typ>()];
+                }
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<const MDLEN: usize> std::io::Write for $name<MDLEN> {
+            fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+                self.update(bytes);
+                Ok(bytes.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        /// Wipes the state, the buffered message and the key.
+        #[cfg(feature = "zeroize")]
+        impl<const MDLEN: usize> Drop for $name<MDLEN> {
+            fn drop(&mut self) {
+                use zeroize::Zeroize;
+                self.h.zeroize();
+                self.buf.zeroize();
+                self.key.zeroize();
+            }
+        }
+    };
+}
+
+blake2_context!(Blake2b, "blake2b", u64, u128, IV_B, 128, 64, 16, 12, 32, 24, 16, 63);
+blake2_context!(Blake2s, "blake2s", u32, u64, IV_S, 64, 32, 8, 10, 16, 12, 8, 7);
+
+pub type Blake2b256 = Blake2b<32>;
+pub type Blake2b512 = Blake2b<64>;
+pub type Blake2s256 = Blake2s<32>;
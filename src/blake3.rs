@@ -0,0 +1,368 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::FinishXOF;
+use crate::OneWayHasher;
+use alloc::vec::Vec;
+
+// https://github.com/BLAKE3-team/BLAKE3-specs/blob/master/blake3.pdf
+// https://github.com/BLAKE3-team/BLAKE3/blob/master/reference_impl/reference_impl.rs
+
+const OUT_LEN: usize = 32;
+const KEY_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+/// The initialization vector, which is the same as the SHA-256 initial values.
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The permutation applied to the message words between rounds.
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/// The mixing function.
+#[inline(always)]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+/// Compression function. Returns the full 16 word state so that it may also be used for
+/// extendable output.
+fn compress(cv: &[u32; 8], block_words: &[u32; 16], counter: u64, block_len: u32, flags: u32) -> [u32; 16] {
+    let mut state: [u32; 16] = [
+        cv[0], cv[1], cv[2], cv[3], cv[4], cv[5], cv[6], cv[7],
+        IV[0], IV[1], IV[2], IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    let mut m: [u32; 16] = *block_words;
+    for r in 0..7 {
+        // mix the columns
+        g(&mut state, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut state, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut state, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut state, 3, 7, 11, 15, m[6], m[7]);
+        // mix the diagonals
+        g(&mut state, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut state, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut state, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut state, 3, 4, 9, 14, m[14], m[15]);
+        if r < 6 {
+            let mut permuted: [u32; 16] = [0; 16];
+            for (i, p) in MSG_PERMUTATION.iter().enumerate() {
+                permuted[i] = m[*p];
+            }
+            m = permuted;
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+fn words_from_le_bytes<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let mut words: [u32; N] = [0; N];
+    for (w, b) in words.iter_mut().zip(bytes.chunks_exact(4)) {
+        *w = u32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+    }
+    words
+}
+
+fn first_8_words(words: [u32; 16]) -> [u32; 8] {
+    let mut cv: [u32; 8] = [0; 8];
+    cv.copy_from_slice(&words[..8]);
+    cv
+}
+
+/// The state needed to produce either a chaining value or any number of root output bytes.
+struct Output {
+    input_cv: [u32; 8],
+    block_words: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_8_words(compress(&self.input_cv, &self.block_words, self.counter, self.block_len, self.flags))
+    }
+
+    fn root_output_bytes(&self, out: &mut [u8]) {
+        for (counter, block) in out.chunks_mut(2 * OUT_LEN).enumerate() {
+            let words: [u32; 16] =
+                compress(&self.input_cv, &self.block_words, counter as u64, self.block_len, self.flags | ROOT);
+            for (word, bytes) in words.iter().zip(block.chunks_mut(4)) {
+                bytes.copy_from_slice(&word.to_le_bytes()[..bytes.len()]);
+            }
+        }
+    }
+}
+
+fn parent_output(left: &[u32; 8], right: &[u32; 8], key_words: &[u32; 8], flags: u32) -> Output {
+    let mut block_words: [u32; 16] = [0; 16];
+    block_words[..8].copy_from_slice(left);
+    block_words[8..].copy_from_slice(right);
+    Output {
+        input_cv: *key_words,
+        block_words,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT | flags,
+    }
+}
+
+struct ChunkState {
+    cv: [u32; 8],
+    chunk_counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key_words: &[u32; 8], chunk_counter: u64, flags: u32) -> Self {
+        Self {
+            cv: *key_words,
+            chunk_counter,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) -> &mut Self {
+        while !data.is_empty() {
+            // the last block of a chunk must be compressed by output(), so only compress a
+            // full buffer once we know more data is coming
+            if self.block_len == BLOCK_LEN {
+                let block_words: [u32; 16] = words_from_le_bytes(&self.block);
+                self.cv = first_8_words(compress(
+                    &self.cv,
+                    &block_words,
+                    self.chunk_counter,
+                    BLOCK_LEN as u32,
+                    self.flags | self.start_flag(),
+                ));
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+            let len: usize = (BLOCK_LEN - self.block_len).min(data.len());
+            self.block[self.block_len..(self.block_len + len)].clone_from_slice(&data[..len]);
+            self.block_len += len;
+            data = &data[len..];
+        }
+        self
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            input_cv: self.cv,
+            block_words: words_from_le_bytes(&self.block),
+            counter: self.chunk_counter,
+            block_len: self.block_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// Returns the length of the left subtree for an input of ```len``` bytes, which is the largest
+/// power of two number of chunks that leaves at least one byte for the right subtree.
+#[cfg(feature = "std")]
+fn left_len(len: usize) -> usize {
+    let full_chunks: usize = (len - 1) / CHUNK_LEN;
+    (1 << (usize::BITS - 1 - full_chunks.leading_zeros())) * CHUNK_LEN
+}
+
+/// Returns the left and right chaining values of a subtree with more than one chunk, hashing
+/// both halves on separate threads when ```threads``` is greater than one.
+#[cfg(feature = "std")]
+fn split_subtree(data: &[u8], key_words: &[u32; 8], chunk_counter: u64, flags: u32, threads: usize) -> ([u32; 8], [u32; 8]) {
+    let (left, right) = data.split_at(left_len(data.len()));
+    let right_counter: u64 = chunk_counter + (left.len() / CHUNK_LEN) as u64;
+    if threads > 1 {
+        std::thread::scope(|s| {
+            let handle = s.spawn(|| subtree_cv(left, key_words, chunk_counter, flags, threads / 2));
+            let right_cv: [u32; 8] = subtree_cv(right, key_words, right_counter, flags, threads - threads / 2);
+            (handle.join().unwrap(), right_cv)
+        })
+    } else {
+        (
+            subtree_cv(left, key_words, chunk_counter, flags, 1),
+            subtree_cv(right, key_words, right_counter, flags, 1),
+        )
+    }
+}
+
+/// Returns the chaining value of a non-root subtree.
+#[cfg(feature = "std")]
+fn subtree_cv(data: &[u8], key_words: &[u32; 8], chunk_counter: u64, flags: u32, threads: usize) -> [u32; 8] {
+    if data.len() <= CHUNK_LEN {
+        ChunkState::new(key_words, chunk_counter, flags).update(data).output().chaining_value()
+    } else {
+        let (left_cv, right_cv) = split_subtree(data, key_words, chunk_counter, flags, threads);
+        parent_output(&left_cv, &right_cv, key_words, flags).chaining_value()
+    }
+}
+
+pub struct Blake3 {
+    chunk_state: ChunkState,
+    key_words: [u32; 8],
+    cv_stack: Vec<[u32; 8]>,
+    flags: u32,
+}
+
+impl Blake3 {
+    fn new_internal(key_words: [u32; 8], flags: u32) -> Self {
+        Self {
+            chunk_state: ChunkState::new(&key_words, 0, flags),
+            key_words,
+            cv_stack: Vec::new(),
+            flags,
+        }
+    }
+
+    /// Returns a new context for the keyed hash mode, which may be used as a MAC or a PRF.
+    pub fn with_key(key: &[u8; KEY_LEN]) -> Self {
+        Self::new_internal(words_from_le_bytes(key), KEYED_HASH)
+    }
+
+    /// Returns a new context for the key derivation mode. The ```context``` string should be
+    /// hardcoded, globally unique, and application specific. The key material is then passed
+    /// to ```update()``` and the derived key is returned by ```finish()``` or ```finish_xof()```.
+    pub fn derive_key(context: &str) -> Self {
+        let mut context_hasher: Blake3 = Self::new_internal(IV, DERIVE_KEY_CONTEXT);
+        context_hasher.update(context.as_bytes());
+        let mut context_key: [u8; KEY_LEN] = [0; KEY_LEN];
+        context_hasher.finish_xof(&mut context_key);
+        Self::new_internal(words_from_le_bytes(&context_key), DERIVE_KEY_MATERIAL)
+    }
+
+    /// Hashes ```data``` in a single call using this context's mode and key, splitting the
+    /// tree across up to ```threads``` threads, and writes any number of output bytes to
+    /// ```digest```. Data previously passed to ```update()``` is not included, and the context
+    /// itself is left unchanged.
+    #[cfg(feature = "std")]
+    pub fn hash_parallel(&self, data: &[u8], threads: usize, digest: &mut [u8]) {
+        let output: Output = if data.len() <= CHUNK_LEN {
+            ChunkState::new(&self.key_words, 0, self.flags).update(data).output()
+        } else {
+            let (left_cv, right_cv) = split_subtree(data, &self.key_words, 0, self.flags, threads);
+            parent_output(&left_cv, &right_cv, &self.key_words, self.flags)
+        };
+        output.root_output_bytes(digest);
+    }
+
+    /// Merges completed subtrees on the stack. The number of trailing zero bits in the total
+    /// number of chunks tells us how many subtrees are now complete.
+    fn add_chunk_cv(&mut self, mut new_cv: [u32; 8], mut total_chunks: u64) {
+        while total_chunks & 1 == 0 {
+            // the stack can never be empty here
+            let left: [u32; 8] = self.cv_stack.pop().unwrap();
+            new_cv = parent_output(&left, &new_cv, &self.key_words, self.flags).chaining_value();
+            total_chunks >>= 1;
+        }
+        self.cv_stack.push(new_cv);
+    }
+
+    fn root_output(&self) -> Output {
+        let mut output: Output = self.chunk_state.output();
+        for left in self.cv_stack.iter().rev() {
+            output = parent_output(left, &output.chaining_value(), &self.key_words, self.flags);
+        }
+        output
+    }
+}
+
+impl OneWayHasher<32> for Blake3 {
+    const NAME: &'static str = "blake3";
+    const BLOCK_SIZE: usize = 64;
+
+    #[inline]
+    fn init() -> Self {
+        Self::new_internal(IV, 0)
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.chunk_state = ChunkState::new(&self.key_words, 0, self.flags);
+        self.cv_stack.clear();
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, mut data: &[u8]) -> &mut Self {
+        while !data.is_empty() {
+            // if the current chunk is complete, finalize it and start a new one
+            if self.chunk_state.len() == CHUNK_LEN {
+                let chunk_cv: [u32; 8] = self.chunk_state.output().chaining_value();
+                let total_chunks: u64 = self.chunk_state.chunk_counter + 1;
+                self.add_chunk_cv(chunk_cv, total_chunks);
+                self.chunk_state = ChunkState::new(&self.key_words, total_chunks, self.flags);
+            }
+            let len: usize = (CHUNK_LEN - self.chunk_state.len()).min(data.len());
+            self.chunk_state.update(&data[..len]);
+            data = &data[len..];
+        }
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        self.root_output().root_output_bytes(digest);
+    }
+}
+
+impl FinishXOF for Blake3 {
+    fn finish_xof(&mut self, digest: &mut [u8]) {
+        self.root_output().root_output_bytes(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Blake3 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
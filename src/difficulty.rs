@@ -0,0 +1,361 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::digest::Digest;
+use crate::error::{Error, ErrorKind, Result};
+use crate::pow::U256;
+
+// https://github.com/bitcoin/bitcoin/blob/master/src/pow.cpp
+// https://upgradespecs.bitcoincashnode.org/2020-11-15-asert/
+// https://github.com/zawy12/difficulty-algorithms/issues/3
+
+/// The fields of a block header that difficulty adjustment depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// The height of the block in the chain. The genesis block is at height 0.
+    pub height: u64,
+    /// The timestamp of the block in seconds.
+    pub time: i64,
+    /// The target of the block in compact "nBits" format. See ```Digest::from_compact()```.
+    pub bits: u32,
+}
+
+/// An algorithm that calculates the target of the next block from the headers before it.
+///
+/// ```headers``` must be consecutive ancestors of the new block, oldest first, ending with its
+/// parent. Passing the last ```window()``` headers is always enough, and fewer are needed near
+/// the start of the chain. ```time``` is the timestamp of the new block, which only some rules
+/// look at. An empty slice means that the new block is the genesis block, whose target is the
+/// proof of work limit. The returned target is in compact "nBits" format, so that it can be
+/// compared with the ```bits``` field of the new header in a ```BlockChainDB::validate()```.
+///
+/// The parameters of every implementation are public, so they are checked on each call, and an
+/// error is returned if they are invalid or if the timestamps are so far apart that the
+/// arithmetic would overflow.
+pub trait Retarget {
+    /// Returns the compact target that the next block must have.
+    fn next_bits(&self, headers: &[Header], time: i64) -> Result<u32>;
+
+    /// Returns the greatest number of recent headers that ```next_bits()``` looks at.
+    fn window(&self) -> usize;
+}
+
+/// Returns the last header, or ```None``` for the genesis block, after checking that the headers
+/// are consecutive.
+fn parent(headers: &[Header]) -> Result<Option<&Header>> {
+    if headers.windows(2).any(|w| w[0].height.checked_add(1) != Some(w[1].height)) {
+        return Err(Error::new(ErrorKind::InvalidIndex, "The headers are not consecutive."));
+    }
+    Ok(headers.last())
+}
+
+/// Decodes a compact target into an integer.
+fn target(bits: u32) -> Result<U256> {
+    Ok(U256::from_le_bytes(&Digest::from_compact(bits)?.0))
+}
+
+/// Returns the error for arithmetic on timestamps or heights that overflows.
+fn overflow() -> Error {
+    Error::new(ErrorKind::IntegerOverflow, "The timestamps or heights are too far apart.")
+}
+
+/// Encodes an integer target in compact form after clamping it to ```pow_limit```.
+fn compact(target: U256, pow_limit: &Digest<32>) -> u32 {
+    Digest(target.min(U256::from_le_bytes(&pow_limit.0)).to_le_bytes()).to_compact()
+}
+
+/// Bitcoin's difficulty adjustment. The target only changes every ```interval()``` blocks, when it
+/// is scaled by how long the last interval took compared to ```target_timespan```, by at most a
+/// factor of four either way. With ```allow_min_difficulty``` the testnet rule applies as well: a
+/// block more than twice the target spacing after its parent may use the proof of work limit, and
+/// the blocks after it go back to the last target that was not the limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinRetarget {
+    /// The easiest allowed target.
+    pub pow_limit: Digest<32>,
+    /// The intended length of an interval in seconds.
+    pub target_timespan: i64,
+    /// The intended time between blocks in seconds.
+    pub target_spacing: i64,
+    /// Whether the testnet 20 minute rule applies.
+    pub allow_min_difficulty: bool,
+}
+
+impl BitcoinRetarget {
+    /// The parameters of Bitcoin mainnet: a two week interval of 2016 ten minute blocks.
+    pub fn mainnet() -> Self {
+        let mut pow_limit: Digest<32> = Digest([0xff; 32]);
+        pow_limit.0[28..].fill(0);
+        Self {
+            pow_limit,
+            target_timespan: 14 * 24 * 60 * 60,
+            target_spacing: 10 * 60,
+            allow_min_difficulty: false,
+        }
+    }
+
+    /// The parameters of Bitcoin testnet3, which are the same as mainnet plus the 20 minute rule.
+    pub fn testnet() -> Self {
+        Self {
+            allow_min_difficulty: true,
+            ..Self::mainnet()
+        }
+    }
+
+    /// Returns the number of blocks between adjustments, or 0 if the parameters are invalid.
+    pub fn interval(&self) -> u64 {
+        match self.check() {
+            Ok(()) => (self.target_timespan / self.target_spacing) as u64,
+            Err(_) => 0,
+        }
+    }
+
+    /// Checks that the target spacing is positive and no longer than the target timespan, and that
+    /// four times the timespan does not overflow.
+    fn check(&self) -> Result<()> {
+        if self.target_spacing <= 0 || self.target_timespan < self.target_spacing {
+            return Err(Error::new(ErrorKind::InvalidParameter, "The target spacing must be positive and not greater than the target timespan."));
+        }
+        if self.target_timespan > i64::MAX / 4 {
+            return Err(Error::new(ErrorKind::InvalidParameter, "The target timespan is too long."));
+        }
+        Ok(())
+    }
+
+    /// Scales the target of ```last```, the final block of an interval, by the time taken since
+    /// ```first_time```, the timestamp of the first block of the interval.
+    pub fn calculate_next_bits(&self, last: &Header, first_time: i64) -> Result<u32> {
+        self.check()?;
+        // saturating does not change the result, since it is clamped anyway
+        let timespan: i64 = last.time.saturating_sub(first_time).clamp(self.target_timespan / 4, self.target_timespan * 4);
+        let (scaled, overflow) = target(last.bits)?.overflowing_mul_u64(timespan as u64);
+        if overflow {
+            return Ok(self.pow_limit.to_compact());
+        }
+        Ok(compact(scaled.div_u64(self.target_timespan as u64), &self.pow_limit))
+    }
+}
+
+impl Retarget for BitcoinRetarget {
+    fn next_bits(&self, headers: &[Header], time: i64) -> Result<u32> {
+        self.check()?;
+        let last: &Header = match parent(headers)? {
+            Some(last) => last,
+            None => return Ok(self.pow_limit.to_compact()),
+        };
+        let interval: u64 = self.interval();
+
+        if last.height.checked_add(1).ok_or_else(overflow)? % interval != 0 {
+            if !self.allow_min_difficulty {
+                return Ok(last.bits);
+            }
+            let limit: u32 = self.pow_limit.to_compact();
+            if time > last.time.checked_add(2 * self.target_spacing).ok_or_else(overflow)? {
+                return Ok(limit);
+            }
+            // go back to the last block that was not mined under the 20 minute rule
+            for h in headers.iter().rev() {
+                if h.height % interval == 0 || h.bits != limit {
+                    return Ok(h.bits);
+                }
+            }
+            return match headers[0].height {
+                0 => Ok(headers[0].bits),
+                _ => Err(Error::new(ErrorKind::BlockNumDoesNotExist, "The headers do not reach back far enough.")),
+            };
+        }
+
+        // the first block of the interval. this off by one is part of Bitcoin's consensus rules
+        let back: usize = (interval - 1) as usize;
+        if headers.len() <= back {
+            return Err(Error::new(ErrorKind::BlockNumDoesNotExist, "The headers do not cover the whole interval."));
+        }
+        let first: &Header = &headers[headers.len() - 1 - back];
+        self.calculate_next_bits(last, first.time)
+    }
+
+    fn window(&self) -> usize {
+        self.interval() as usize
+    }
+}
+
+/// The block that ASERT calculates every target from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsertAnchor {
+    /// The height of the anchor block.
+    pub height: u64,
+    /// The timestamp of the parent of the anchor block.
+    pub parent_time: i64,
+    /// The target of the anchor block in compact form.
+    pub bits: u32,
+}
+
+/// The absolutely scheduled exponentially rising targets algorithm (aserti3-2d) of Bitcoin Cash.
+/// The target doubles for every ```half_life``` seconds that the chain falls behind the ideal
+/// schedule since the anchor block, and halves for every ```half_life``` it gets ahead. Only the
+/// parent of the new block is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asert {
+    /// The easiest allowed target.
+    pub pow_limit: Digest<32>,
+    /// The anchor block.
+    pub anchor: AsertAnchor,
+    /// The intended time between blocks in seconds.
+    pub target_spacing: i64,
+    /// The number of seconds it takes for the target to double or halve.
+    pub half_life: i64,
+}
+
+impl Asert {
+    /// The parameters of Bitcoin Cash mainnet, which activated ASERT in November 2020.
+    pub fn bitcoin_cash() -> Self {
+        Self {
+            pow_limit: BitcoinRetarget::mainnet().pow_limit,
+            anchor: AsertAnchor {
+                height: 661647,
+                parent_time: 1605447844,
+                bits: 0x1804dafe,
+            },
+            target_spacing: 10 * 60,
+            half_life: 2 * 24 * 60 * 60,
+        }
+    }
+
+    /// Returns the target of the block after ```parent```.
+    pub fn calculate_next_bits(&self, parent: &Header) -> Result<u32> {
+        if self.target_spacing <= 0 || self.half_life <= 0 {
+            return Err(Error::new(ErrorKind::InvalidParameter, "The target spacing and half life must be positive."));
+        }
+        if parent.height < self.anchor.height {
+            return Err(Error::new(ErrorKind::BlockNumDoesNotExist, "The parent is before the anchor block."));
+        }
+        let anchor_target: U256 = target(self.anchor.bits)?;
+        if anchor_target > U256::from_le_bytes(&self.pow_limit.0) {
+            return Err(Error::new(ErrorKind::InvalidParameter, "The anchor target is greater than the proof of work limit."));
+        }
+        // leave room for the factor below, which is less than 2^17
+        if anchor_target.bits() > 239 {
+            return Err(Error::new(ErrorKind::InvalidParameter, "The anchor target is too large."));
+        }
+        let time_diff: i64 = parent.time.checked_sub(self.anchor.parent_time).ok_or_else(overflow)?;
+        let height_diff: i64 = i64::try_from(parent.height - self.anchor.height).map_err(|_| overflow())?;
+
+        // a 16.16 fixed point exponent. the division truncates and the shift floors, as in the spec
+        let exponent: i64 = height_diff
+            .checked_add(1)
+            .and_then(|blocks| self.target_spacing.checked_mul(blocks))
+            .and_then(|ideal| time_diff.checked_sub(ideal))
+            .and_then(|behind| behind.checked_mul(65536))
+            .ok_or_else(overflow)?
+            / self.half_life;
+        let mut shifts: i64 = exponent >> 16;
+        let frac: u128 = (exponent as u16) as u128;
+
+        // 65536 * 2^frac, by a cubic approximation that is accurate to within 0.013%
+        let factor: u64 = 65536
+            + ((195766423245049 * frac + 971821376 * frac * frac + 5127 * frac * frac * frac + (1 << 47)) >> 48) as u64;
+
+        // the anchor target is below 2^239, so this cannot overflow
+        let mut next: U256 = anchor_target.overflowing_mul_u64(factor).0;
+        shifts -= 16;
+        if shifts <= 0 {
+            next = next.shr(shifts.unsigned_abs().min(256) as u32);
+        } else if shifts >= 256 || next.bits() + shifts as u32 > 256 {
+            next = U256::from_le_bytes(&self.pow_limit.0);
+        } else {
+            next = next.shl(shifts as u32);
+        }
+        if next.is_zero() {
+            // zero is not a valid target, but one is
+            next = U256::ONE;
+        }
+        Ok(compact(next, &self.pow_limit))
+    }
+}
+
+impl Retarget for Asert {
+    fn next_bits(&self, headers: &[Header], _time: i64) -> Result<u32> {
+        match parent(headers)? {
+            Some(parent) => self.calculate_next_bits(parent),
+            None => Ok(self.pow_limit.to_compact()),
+        }
+    }
+
+    fn window(&self) -> usize {
+        1
+    }
+}
+
+/// The linearly weighted moving average algorithm (LWMA-1) by zawy12. The next target is the
+/// average target of the last ```window``` blocks scaled by their solve times, with the most
+/// recent solve times weighted the most. Solve times are capped at six times the target spacing,
+/// and out of order timestamps count as one second.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lwma {
+    /// The easiest allowed target, which is also used until the chain is longer than the window.
+    pub pow_limit: Digest<32>,
+    /// The number of blocks averaged over.
+    pub window: u64,
+    /// The intended time between blocks in seconds.
+    pub target_spacing: i64,
+}
+
+impl Lwma {
+    /// Checks the parameters and returns ```k```, the weighted sum of the target solve times, i.e.
+    /// ```window * (window + 1) * target_spacing / 2```. The weighted sum of the capped solve times
+    /// is at most six times ```k```, so limiting ```k``` keeps that sum from overflowing too.
+    fn check(&self) -> Result<u64> {
+        if self.window == 0 || self.target_spacing <= 0 {
+            return Err(Error::new(ErrorKind::InvalidParameter, "The window and target spacing must be positive."));
+        }
+        let k: Option<u64> = self
+            .window
+            .checked_add(1)
+            .and_then(|w| w.checked_mul(self.window))
+            .and_then(|w| w.checked_mul(self.target_spacing as u64))
+            .map(|w| w / 2);
+        match k {
+            Some(k) if k <= i64::MAX as u64 / 6 && k.checked_mul(self.window).is_some() => Ok(k),
+            _ => Err(Error::new(ErrorKind::InvalidParameter, "The window or target spacing is too large.")),
+        }
+    }
+}
+
+impl Retarget for Lwma {
+    fn next_bits(&self, headers: &[Header], _time: i64) -> Result<u32> {
+        let k: u64 = self.check()?;
+        match parent(headers)? {
+            Some(last) if last.height >= self.window => (),
+            _ => return Ok(self.pow_limit.to_compact()),
+        }
+        let n: usize = self.window as usize;
+        if headers.len() <= n {
+            return Err(Error::new(ErrorKind::BlockNumDoesNotExist, "The headers do not cover the whole window."));
+        }
+        let t: i64 = self.target_spacing;
+
+        let mut weighted_time: i64 = 0;
+        let mut sum_target: U256 = U256::ZERO;
+        let mut previous_time: i64 = headers[headers.len() - 1 - n].time;
+        for (j, h) in headers[(headers.len() - n)..].iter().enumerate() {
+            let this_time: i64 = if h.time > previous_time { h.time } else { previous_time.checked_add(1).ok_or_else(overflow)? };
+            // saturating does not change the result, since it is capped anyway
+            let solve_time: i64 = this_time.saturating_sub(previous_time).min(6 * t);
+            previous_time = this_time;
+            weighted_time += solve_time * (j as i64 + 1);
+            // dividing by k here keeps the sum from overflowing
+            sum_target = sum_target.overflowing_add(target(h.bits)?.div_u64(k * self.window)).0;
+        }
+
+        let (next, overflow) = sum_target.overflowing_mul_u64(weighted_time as u64);
+        if overflow {
+            return Ok(self.pow_limit.to_compact());
+        }
+        Ok(compact(next, &self.pow_limit))
+    }
+
+    fn window(&self) -> usize {
+        (self.window as usize).saturating_add(1)
+    }
+}
@@ -2,11 +2,19 @@
 // Distributed under the MIT software license, see the accompanying
 // file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
 
+#[cfg(feature = "alloc")]
+use crate::dynamic::Algorithm;
+#[cfg(feature = "alloc")]
+use crate::encoding::{self, Base64, Bech32Variant};
 use crate::error::{Error, ErrorKind};
-use std::cmp::Ordering;
-use std::fmt::Display;
-use std::ops::{Deref, DerefMut, Index, IndexMut};
-use std::str::FromStr;
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Display;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub struct Digest<const S: usize>(pub [u8; S]);
@@ -32,12 +40,11 @@ impl<const S: usize> PartialEq<[u8; S]> for Digest<S> {
 impl<const S: usize> Eq for Digest<S> {}
 
 impl<const S: usize> Display for Digest<S> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut data: String = String::new();
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for n in self.0 {
-            data.push_str(&format!("{:02x}", n));
+            write!(f, "{:02x}", n)?;
         }
-        f.write_str(&data)
+        Ok(())
     }
 }
 
@@ -87,7 +94,7 @@ impl<const S: usize> TryFrom<&[u8]> for Digest<S> {
             digest.0.clone_from_slice(src);
             Ok(digest)
         } else {
-            Err(Error::new(ErrorKind::InvalidSliceLength, &format!("Slice lenght is not equal to digest length of {}.", S)))
+            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice length is not equal to the digest length."))
         }
     }
 }
@@ -104,15 +111,18 @@ impl<const S: usize> FromStr for Digest<S> {
     /// in hexidecimal format and may include the "0x" prefix. Ok(Digest) is returned on success.
     ///  Err(String) is returned on failure.
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let s: String = string.to_ascii_lowercase();
-        let mut src: &str = s.trim();
-        if let Some(s) = src.strip_prefix("0x") {
+        let mut src: &str = string.trim();
+        if let Some(s) = src.strip_prefix("0x").or_else(|| src.strip_prefix("0X")) {
             src = s
         }
         match src.len().cmp(&(S * 2)) {
             Ordering::Greater => Err(Error::new(ErrorKind::StringTooLong, "")),
             Ordering::Less => Err(Error::new(ErrorKind::StringTooShort, "")),
             Ordering::Equal => {
+                // checking every byte first also keeps the slicing below on character boundaries
+                if !src.bytes().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(Error::new(ErrorKind::InvalidHexString, "The string contains a character that is not a hexidecimal digit."));
+                }
                 let mut digest: Digest<S> = Digest::new();
                 for (i, offset) in (0..(S * 2)).step_by(2).enumerate() {
                     digest.0[i] = u8::from_str_radix(&src[offset..(offset + 2)], 16)?
@@ -133,10 +143,10 @@ impl<const S: usize> Digest<S> {
         self.0.len()
     }
 
-    pub fn from_bytes(bytes: &mut [u8]) -> std::result::Result<Digest<S>, Error> {
+    pub fn from_bytes(bytes: &mut [u8]) -> core::result::Result<Digest<S>, Error> {
         match bytes.len().cmp(&S) {
-            Ordering::Greater => Err(Error::new(ErrorKind::SliceTooLong, &format!("The byte slice is longer than the digest length of {}", S))),
-            Ordering::Less => Err(Error::new(ErrorKind::SliceTooShort, &format!("The byte slice is shorter than the digest length of {}", S))),
+            Ordering::Greater => Err(Error::new(ErrorKind::SliceTooLong, "The byte slice is longer than the digest length.")),
+            Ordering::Less => Err(Error::new(ErrorKind::SliceTooShort, "The byte slice is shorter than the digest length.")),
             Ordering::Equal => {
                 let mut digest: Digest<S> = Digest::new();
                 digest.0.clone_from_slice(bytes);
@@ -152,4 +162,211 @@ impl<const S: usize> Digest<S> {
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
         &mut self.0[..]
     }
+
+    /// Compares the digest to ```other``` in constant time. Use this rather than ```==``` when
+    /// either side is secret, e.g. a MAC tag. See ```digest::ct_eq()```.
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        ct_eq(&self.0, other)
+    }
+
+    /// Returns a copy of the digest with the order of its bytes reversed.
+    pub fn reversed(&self) -> Self {
+        let mut digest: Digest<S> = self.clone();
+        digest.0.reverse();
+        digest
+    }
+
+    /// Returns the digest as a hexidecimal string with its bytes in reverse order. This is how
+    /// Bitcoin displays block hashes, transaction ids, and merkle roots.
+    #[cfg(feature = "alloc")]
+    pub fn to_rev_string(&self) -> String {
+        self.reversed().to_string()
+    }
+
+    /// Parses a hexidecimal string with its bytes in reverse order, such as a block hash or
+    /// transaction id copied from a Bitcoin block explorer.
+    pub fn from_rev_str(string: &str) -> core::result::Result<Digest<S>, Error> {
+        Ok(Digest::from_str(string)?.reversed())
+    }
+
+    /// Encodes the digest as a multihash for ```algorithm```. See ```multihash::encode()```.
+    #[cfg(feature = "alloc")]
+    pub fn to_multihash(&self, algorithm: Algorithm) -> core::result::Result<Vec<u8>, Error> {
+        crate::multihash::encode(algorithm, &self.0)
+    }
+
+    /// Decodes a multihash, returning the algorithm and the digest. Fails if the length of the
+    /// digest is not ```S```. See ```multihash::decode()```.
+    #[cfg(feature = "alloc")]
+    pub fn from_multihash(bytes: &[u8]) -> core::result::Result<(Algorithm, Digest<S>), Error> {
+        let (algorithm, digest) = crate::multihash::decode(bytes)?;
+        if digest.len() != S {
+            Err(Error::new(ErrorKind::InvalidDigestLength, "The multihash digest length is not equal to the digest length."))
+        } else {
+            let mut d: Digest<S> = Digest::new();
+            d.0.copy_from_slice(digest);
+            Ok((algorithm, d))
+        }
+    }
+
+    /// Copies decoded bytes into a new digest. Fails if there are not exactly ```S``` bytes.
+    #[cfg(feature = "alloc")]
+    fn from_decoded(bytes: &[u8]) -> core::result::Result<Digest<S>, Error> {
+        if bytes.len() != S {
+            Err(Error::new(ErrorKind::InvalidDigestLength, "The decoded length is not equal to the digest length."))
+        } else {
+            let mut digest: Digest<S> = Digest::new();
+            digest.0.copy_from_slice(bytes);
+            Ok(digest)
+        }
+    }
+
+    /// Returns the digest as a Base58 string. See ```encoding::base58_encode()```.
+    #[cfg(feature = "alloc")]
+    pub fn to_base58(&self) -> String {
+        encoding::base58_encode(&self.0)
+    }
+
+    /// Parses a Base58 string. See ```encoding::base58_decode()```.
+    #[cfg(feature = "alloc")]
+    pub fn from_base58(string: &str) -> core::result::Result<Digest<S>, Error> {
+        Self::from_decoded(&encoding::base58_decode(string)?)
+    }
+
+    /// Returns ```prefix``` followed by the digest as a Base58Check string. With a Hash160 digest
+    /// and a prefix of ```[0x00]``` this is a Bitcoin P2PKH address.
+    #[cfg(feature = "alloc")]
+    pub fn to_base58check(&self, prefix: &[u8]) -> String {
+        let mut payload: Vec<u8> = Vec::with_capacity(prefix.len() + S);
+        payload.extend_from_slice(prefix);
+        payload.extend_from_slice(&self.0);
+        encoding::base58check_encode(&payload)
+    }
+
+    /// Parses a Base58Check string whose payload is ```prefix``` followed by the digest. Fails if
+    /// the checksum does not match or the payload does not start with ```prefix```.
+    #[cfg(feature = "alloc")]
+    pub fn from_base58check(string: &str, prefix: &[u8]) -> core::result::Result<Digest<S>, Error> {
+        let payload: Vec<u8> = encoding::base58check_decode(string)?;
+        match payload.strip_prefix(prefix) {
+            Some(bytes) => Self::from_decoded(bytes),
+            None => Err(Error::new(ErrorKind::InvalidBase58, "The payload does not start with the expected prefix.")),
+        }
+    }
+
+    /// Returns the digest as a Base64 string. See ```encoding::base64_encode()```.
+    #[cfg(feature = "alloc")]
+    pub fn to_base64(&self, alphabet: Base64) -> String {
+        encoding::base64_encode(&self.0, alphabet)
+    }
+
+    /// Parses a Base64 string. See ```encoding::base64_decode()```.
+    #[cfg(feature = "alloc")]
+    pub fn from_base64(string: &str, alphabet: Base64) -> core::result::Result<Digest<S>, Error> {
+        Self::from_decoded(&encoding::base64_decode(string, alphabet)?)
+    }
+
+    /// Returns the digest as a Bech32 or Bech32m string with the human readable part ```hrp```.
+    /// The bytes of the digest are regrouped into 5 bit values, padding the last one with zeros.
+    #[cfg(feature = "alloc")]
+    pub fn to_bech32(&self, hrp: &str, variant: Bech32Variant) -> core::result::Result<String, Error> {
+        encoding::bech32_encode(hrp, &encoding::convert_bits(&self.0, 8, 5, true)?, variant)
+    }
+
+    /// Parses a Bech32 or Bech32m string, returning the human readable part and the digest. Fails
+    /// if the checksum is not of the given variant.
+    #[cfg(feature = "alloc")]
+    pub fn from_bech32(string: &str, variant: Bech32Variant) -> core::result::Result<(String, Digest<S>), Error> {
+        let (hrp, data, found) = encoding::bech32_decode(string)?;
+        if found != variant {
+            return Err(Error::new(ErrorKind::InvalidChecksum, "The checksum is not of the expected Bech32 variant."));
+        }
+        Ok((hrp, Self::from_decoded(&encoding::convert_bits(&data, 5, 8, false)?)?))
+    }
+}
+
+/// Compares two byte slices in constant time, i.e. the time taken depends only on their lengths
+/// and not on where they differ. Slices of different lengths are never equal.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff: u8 = a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
+
+#[cfg(feature = "zeroize")]
+impl<const S: usize> zeroize::Zeroize for Digest<S> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Serializes byte arrays as hexidecimal strings in human-readable formats such as JSON, and as
+/// raw bytes otherwise. Used for ```Digest``` and with ```#[serde(with = "...")]``` elsewhere.
+#[cfg(feature = "serde")]
+pub(crate) mod hex_or_bytes {
+    use super::Digest;
+    use core::str::FromStr;
+    use serde::de::{Error, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Digest(*bytes))
+        } else {
+            serializer.serialize_bytes(bytes)
+        }
+    }
+
+    struct ArrayVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+        type Value = [u8; N];
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            write!(f, "{} bytes or a hexidecimal string of {} characters", N, N * 2)
+        }
+
+        fn visit_str<E: Error>(self, string: &str) -> Result<Self::Value, E> {
+            Digest::<N>::from_str(string).map(|d| d.0).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+            bytes.try_into().map_err(|_| E::invalid_length(bytes.len(), &self))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes: [u8; N] = [0; N];
+            for (i, b) in bytes.iter_mut().enumerate() {
+                *b = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(A::Error::invalid_length(N + 1, &self));
+            }
+            Ok(bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ArrayVisitor::<N>)
+        } else {
+            deserializer.deserialize_bytes(ArrayVisitor::<N>)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const S: usize> serde::Serialize for Digest<S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        hex_or_bytes::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const S: usize> serde::Deserialize<'de> for Digest<S> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        hex_or_bytes::deserialize(deserializer).map(Digest)
+    }
 }
@@ -0,0 +1,190 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::blake2::{Blake2b256, Blake2b512, Blake2s256};
+use crate::blake3::Blake3;
+use crate::error::{Error, ErrorKind, Result};
+use crate::ripemd::{Hash160, Ripemd160};
+use crate::sha2::{Sha224, Sha256, Sha256d, Sha384, Sha512, Sha512_224, Sha512_256};
+use crate::sha3::{Keccak224, Keccak256, Keccak384, Keccak512, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256};
+use crate::OneWayHasher;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
+
+/// An object safe version of ```OneWayHasher```, which allows the algorithm to be chosen at
+/// runtime, e.g. ```let hasher: Box<dyn DynHasher> = Algorithm::from_str("sha3-256")?.new_hasher();```.
+pub trait DynHasher {
+    /// Absorbs more data.
+    fn update(&mut self, data: &[u8]);
+
+    /// Resets the hasher to its initial state.
+    fn reset(&mut self);
+
+    /// Finalizes the hash and returns the digest. Call ```reset()``` before using the hasher again.
+    fn finalize_into_vec(&mut self) -> Vec<u8>;
+
+    /// Returns the length of the digest in bytes.
+    fn output_size(&self) -> usize;
+
+    /// Returns the block size of the algorithm in bytes.
+    fn block_size(&self) -> usize;
+
+    /// Returns the name of the algorithm.
+    fn name(&self) -> &'static str;
+}
+
+/// Wraps any ```OneWayHasher``` so that it can be used as a ```DynHasher```.
+pub struct DynWrapper<H: OneWayHasher<MDLEN>, const MDLEN: usize>(pub H);
+
+impl<H: OneWayHasher<MDLEN>, const MDLEN: usize> From<H> for DynWrapper<H, MDLEN> {
+    fn from(hasher: H) -> Self {
+        Self(hasher)
+    }
+}
+
+impl<H: OneWayHasher<MDLEN>, const MDLEN: usize> DynHasher for DynWrapper<H, MDLEN> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    #[inline]
+    fn finalize_into_vec(&mut self) -> Vec<u8> {
+        Vec::from(self.0.finish().0)
+    }
+
+    #[inline]
+    fn output_size(&self) -> usize {
+        MDLEN
+    }
+
+    #[inline]
+    fn block_size(&self) -> usize {
+        H::BLOCK_SIZE
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        H::NAME
+    }
+}
+
+/// Defines the ```Algorithm``` enum from a list of variants along with the hasher and digest
+/// length of each one.
+macro_rules! algorithms {
+    ($($variant:ident => $hasher:ty, $mdlen:literal;)*) => {
+        /// The hash algorithms that can be chosen by name at runtime. The names are the same as
+        /// ```OneWayHasher::NAME``` of the corresponding hashers.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Algorithm {
+            $($variant,)*
+        }
+
+        impl Algorithm {
+            /// Every algorithm in the registry.
+            pub const ALL: &'static [Algorithm] = &[$(Algorithm::$variant,)*];
+
+            /// Returns the name of the algorithm, e.g. ```"sha512/256"```.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Algorithm::$variant => <$hasher as OneWayHasher<$mdlen>>::NAME,)*
+                }
+            }
+
+            /// Returns the length of the digest in bytes.
+            pub fn output_size(&self) -> usize {
+                match self {
+                    $(Algorithm::$variant => $mdlen,)*
+                }
+            }
+
+            /// Returns the block size of the algorithm in bytes.
+            pub fn block_size(&self) -> usize {
+                match self {
+                    $(Algorithm::$variant => <$hasher as OneWayHasher<$mdlen>>::BLOCK_SIZE,)*
+                }
+            }
+
+            /// Returns a new boxed hasher for the algorithm.
+            pub fn new_hasher(&self) -> Box<dyn DynHasher> {
+                match self {
+                    $(Algorithm::$variant => Box::new(DynWrapper::<$hasher, $mdlen>(<$hasher>::init())),)*
+                }
+            }
+        }
+    };
+}
+
+algorithms! {
+    Sha224 => Sha224, 28;
+    Sha256 => Sha256, 32;
+    Sha384 => Sha384, 48;
+    Sha512 => Sha512, 64;
+    Sha512_224 => Sha512_224, 28;
+    Sha512_256 => Sha512_256, 32;
+    Sha256d => Sha256d, 32;
+    Sha3_224 => Sha3_224, 28;
+    Sha3_256 => Sha3_256, 32;
+    Sha3_384 => Sha3_384, 48;
+    Sha3_512 => Sha3_512, 64;
+    Keccak224 => Keccak224, 28;
+    Keccak256 => Keccak256, 32;
+    Keccak384 => Keccak384, 48;
+    Keccak512 => Keccak512, 64;
+    Shake128 => Shake128<32>, 32;
+    Shake256 => Shake256<64>, 64;
+    Blake2b256 => Blake2b256, 32;
+    Blake2b512 => Blake2b512, 64;
+    Blake2s256 => Blake2s256, 32;
+    Blake3 => Blake3, 32;
+    Ripemd160 => Ripemd160, 20;
+    Hash160 => Hash160, 20;
+}
+
+impl Algorithm {
+    /// Returns the digest of ```data```.
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let mut hasher: Box<dyn DynHasher> = self.new_hasher();
+        hasher.update(data);
+        hasher.finalize_into_vec()
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    /// Looks up an algorithm by name. The comparison ignores case and treats ```'-'```, ```'_'```
+    /// and ```'/'``` as equal, so ```"SHA512_256"``` finds ```"sha512/256"```.
+    fn from_str(name: &str) -> core::result::Result<Self, Self::Err> {
+        fn normalize(c: char) -> char {
+            match c {
+                '_' | '/' => '-',
+                c => c.to_ascii_lowercase(),
+            }
+        }
+        Algorithm::ALL
+            .iter()
+            .find(|a| a.name().len() == name.len() && a.name().chars().map(normalize).eq(name.chars().map(normalize)))
+            .copied()
+            .ok_or(Error::new(ErrorKind::UnknownAlgorithm, "There is no hash algorithm with that name."))
+    }
+}
+
+/// Returns a new boxed hasher for the algorithm called ```name```. See ```Algorithm::from_str()```.
+pub fn new_hasher(name: &str) -> Result<Box<dyn DynHasher>> {
+    Ok(Algorithm::from_str(name)?.new_hasher())
+}
@@ -0,0 +1,299 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::sha2::Sha256d;
+use crate::OneWayHasher;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// https://en.bitcoin.it/wiki/Base58Check_encoding
+// https://datatracker.ietf.org/doc/html/rfc4648
+// https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+// https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Returns the value of a Base58 character, or ```None``` if it is not in the alphabet.
+fn base58_value(c: u8) -> Option<u8> {
+    BASE58_ALPHABET.iter().position(|a| *a == c).map(|i| i as u8)
+}
+
+/// Encodes ```bytes``` as Base58. Each leading zero byte becomes a leading ```'1'```.
+pub fn base58_encode(bytes: &[u8]) -> String {
+    let zeros: usize = bytes.iter().take_while(|b| **b == 0).count();
+
+    // the digits of the number in base 58, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for b in &bytes[zeros..] {
+        let mut carry: u32 = *b as u32;
+        for d in digits.iter_mut() {
+            carry += (*d as u32) << 8;
+            *d = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut string: String = String::with_capacity(zeros + digits.len());
+    string.extend(core::iter::repeat('1').take(zeros));
+    string.extend(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize] as char));
+    string
+}
+
+/// Decodes a Base58 string. Each leading ```'1'``` becomes a leading zero byte.
+pub fn base58_decode(string: &str) -> Result<Vec<u8>> {
+    let zeros: usize = string.bytes().take_while(|c| *c == b'1').count();
+
+    // the bytes of the number, least significant first
+    let mut bytes: Vec<u8> = Vec::with_capacity(string.len() * 733 / 1000 + 1);
+    for c in string.bytes().skip(zeros) {
+        let mut carry: u32 = base58_value(c)
+            .ok_or(Error::new(ErrorKind::InvalidBase58, "The string contains a character that is not in the Base58 alphabet."))?
+            as u32;
+        for b in bytes.iter_mut() {
+            carry += (*b as u32) * 58;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    bytes.extend(core::iter::repeat(0).take(zeros));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// Returns the first four bytes of the double SHA-256 of ```payload```.
+fn base58_checksum(payload: &[u8]) -> [u8; 4] {
+    let digest = Sha256d::digest(payload);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// Encodes ```payload``` as Base58Check, i.e. the Base58 encoding of the payload followed by the
+/// first four bytes of its double SHA-256. Version bytes, if any, are part of the payload.
+pub fn base58check_encode(payload: &[u8]) -> String {
+    let mut bytes: Vec<u8> = Vec::with_capacity(payload.len() + 4);
+    bytes.extend_from_slice(payload);
+    bytes.extend_from_slice(&base58_checksum(payload));
+    base58_encode(&bytes)
+}
+
+/// Decodes a Base58Check string, verifies the checksum, and returns the payload.
+pub fn base58check_decode(string: &str) -> Result<Vec<u8>> {
+    let mut bytes: Vec<u8> = base58_decode(string)?;
+    if bytes.len() < 4 {
+        return Err(Error::new(ErrorKind::InvalidBase58, "The string is too short to contain a checksum."));
+    }
+    let checksum: Vec<u8> = bytes.split_off(bytes.len() - 4);
+    if checksum != base58_checksum(&bytes) {
+        return Err(Error::new(ErrorKind::InvalidChecksum, "The Base58Check checksum does not match the payload."));
+    }
+    Ok(bytes)
+}
+
+/// The two Base64 alphabets of RFC 4648.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64 {
+    /// The standard alphabet with ```'+'``` and ```'/'```. Encoded strings are padded with ```'='```.
+    Standard,
+    /// The URL and filename safe alphabet with ```'-'``` and ```'_'```. Encoded strings are not padded.
+    UrlSafe,
+}
+
+impl Base64 {
+    fn alphabet(&self) -> &'static [u8; 64] {
+        match self {
+            Base64::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+            Base64::UrlSafe => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+        }
+    }
+
+    fn value(&self, c: u8) -> Option<u32> {
+        self.alphabet().iter().position(|a| *a == c).map(|i| i as u32)
+    }
+}
+
+/// Encodes ```bytes``` as Base64 with the given alphabet.
+pub fn base64_encode(bytes: &[u8], alphabet: Base64) -> String {
+    let table: &[u8; 64] = alphabet.alphabet();
+    let mut string: String = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n: u32 = chunk.iter().enumerate().fold(0, |n, (i, b)| n | ((*b as u32) << (16 - 8 * i)));
+        for i in 0..(chunk.len() + 1) {
+            string.push(table[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+        }
+        if alphabet == Base64::Standard {
+            string.extend(core::iter::repeat('=').take(3 - chunk.len()));
+        }
+    }
+    string
+}
+
+/// Decodes a Base64 string with the given alphabet. Padding is optional, but if present it must
+/// be correct. Strings whose unused trailing bits are not zero are rejected, so that each byte
+/// string has exactly one encoding.
+pub fn base64_decode(string: &str, alphabet: Base64) -> Result<Vec<u8>> {
+    let unpadded: &str = string.trim_end_matches('=');
+    let padding: usize = string.len() - unpadded.len();
+    if padding > 2 || (padding > 0 && string.len() % 4 != 0) {
+        return Err(Error::new(ErrorKind::InvalidBase64, "The string is not padded correctly."));
+    }
+    if unpadded.len() % 4 == 1 {
+        return Err(Error::new(ErrorKind::InvalidBase64, "The length of the string is invalid."));
+    }
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(unpadded.len() * 3 / 4);
+    for chunk in unpadded.as_bytes().chunks(4) {
+        let mut n: u32 = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            let value: u32 = alphabet
+                .value(*c)
+                .ok_or(Error::new(ErrorKind::InvalidBase64, "The string contains a character that is not in the Base64 alphabet."))?;
+            n |= value << (18 - 6 * i);
+        }
+        let len: usize = chunk.len() - 1;
+        if n & (0xffffff >> (8 * len)) != 0 {
+            return Err(Error::new(ErrorKind::InvalidBase64, "The unused trailing bits are not zero."));
+        }
+        bytes.extend((0..len).map(|i| (n >> (16 - 8 * i)) as u8));
+    }
+    Ok(bytes)
+}
+
+/// The two checksum variants of Bech32. Bech32m is used for segwit version 1 and later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bech32Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Bech32Variant {
+    /// The value that the checksum polymod must equal.
+    fn constant(&self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+const BECH32_ALPHABET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Updates the BCH checksum with the 5 bit values in ```values```.
+fn bech32_polymod(mut chk: u32, values: impl Iterator<Item = u8>) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    for v in values {
+        let top: u32 = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ (v as u32);
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// Returns the checksum polymod of the human readable part and the data.
+fn bech32_checksum(hrp: &[u8], data: &[u8]) -> u32 {
+    let expanded = hrp.iter().map(|c| c >> 5).chain([0]).chain(hrp.iter().map(|c| c & 0x1f));
+    bech32_polymod(1, expanded.chain(data.iter().copied()))
+}
+
+/// Regroups ```data``` from ```from``` bit values into ```to``` bit values. When ```pad``` is true
+/// the last group is padded with zeros, otherwise any leftover bits must be zero padding that
+/// is shorter than ```from``` bits.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max: u32 = (1 << to) - 1;
+    let max_acc: u32 = (1 << (from + to - 1)) - 1;
+    let mut out: Vec<u8> = Vec::with_capacity((data.len() * from as usize).div_ceil(to as usize));
+    for value in data {
+        if (*value as u32) >> from != 0 {
+            return Err(Error::new(ErrorKind::InvalidBech32, "A value is wider than the number of bits."));
+        }
+        acc = ((acc << from) | (*value as u32)) & max_acc;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return Err(Error::new(ErrorKind::InvalidBech32, "The padding is invalid."));
+    }
+    Ok(out)
+}
+
+/// Encodes the 5 bit values in ```data``` as Bech32 or Bech32m with the human readable part
+/// ```hrp```, which is converted to lowercase. The 90 character limit of BIP173 is not enforced,
+/// so that 64 byte digests can be encoded too.
+pub fn bech32_encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> Result<String> {
+    let hrp: Vec<u8> = hrp.bytes().map(|c| c.to_ascii_lowercase()).collect();
+    if hrp.is_empty() || hrp.len() > 83 || hrp.iter().any(|c| !(33..=126).contains(c)) {
+        return Err(Error::new(ErrorKind::InvalidBech32, "The human readable part is invalid."));
+    }
+    if data.iter().any(|v| *v > 31) {
+        return Err(Error::new(ErrorKind::InvalidBech32, "The data contains a value that is wider than 5 bits."));
+    }
+    let chk: u32 = bech32_polymod(bech32_checksum(&hrp, data), [0; 6].into_iter()) ^ variant.constant();
+
+    let mut string: String = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+    string.extend(hrp.iter().map(|c| *c as char));
+    string.push('1');
+    string.extend(data.iter().map(|v| BECH32_ALPHABET[*v as usize] as char));
+    string.extend((0..6).map(|i| BECH32_ALPHABET[((chk >> (5 * (5 - i))) & 0x1f) as usize] as char));
+    Ok(string)
+}
+
+/// Decodes a Bech32 or Bech32m string, returning the lowercase human readable part, the 5 bit
+/// data values without the checksum, and the variant whose checksum matched. Strings that mix
+/// upper and lower case are rejected.
+pub fn bech32_decode(string: &str) -> Result<(String, Vec<u8>, Bech32Variant)> {
+    let has_lower: bool = string.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper: bool = string.bytes().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Error::new(ErrorKind::InvalidBech32, "The string mixes upper and lower case."));
+    }
+    let string: String = string.to_ascii_lowercase();
+    let sep: usize = string
+        .rfind('1')
+        .ok_or(Error::new(ErrorKind::InvalidBech32, "The string does not contain the separator."))?;
+    let (hrp, rest) = (&string.as_bytes()[..sep], &string.as_bytes()[(sep + 1)..]);
+    if hrp.is_empty() || hrp.len() > 83 || hrp.iter().any(|c| !(33..=126).contains(c)) {
+        return Err(Error::new(ErrorKind::InvalidBech32, "The human readable part is invalid."));
+    }
+    if rest.len() < 6 {
+        return Err(Error::new(ErrorKind::InvalidBech32, "The string is too short to contain a checksum."));
+    }
+
+    let mut data: Vec<u8> = Vec::with_capacity(rest.len());
+    for c in rest {
+        let value: usize = BECH32_ALPHABET
+            .iter()
+            .position(|a| a == c)
+            .ok_or(Error::new(ErrorKind::InvalidBech32, "The string contains a character that is not in the Bech32 alphabet."))?;
+        data.push(value as u8);
+    }
+
+    let variant: Bech32Variant = match bech32_checksum(hrp, &data) {
+        c if c == Bech32Variant::Bech32.constant() => Bech32Variant::Bech32,
+        c if c == Bech32Variant::Bech32m.constant() => Bech32Variant::Bech32m,
+        _ => return Err(Error::new(ErrorKind::InvalidChecksum, "The Bech32 checksum does not match.")),
+    };
+    data.truncate(data.len() - 6);
+    Ok((String::from(&string[..sep]), data, variant))
+}
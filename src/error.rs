@@ -2,7 +2,7 @@
 // Distributed under the MIT software license, see the accompanying
 // file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
 
-use std::fmt::Display;
+use core::fmt::Display;
 
 /// An enumeration of the various error types used throughout ```bc_hash```.
 #[derive(Debug, Clone)]
@@ -12,28 +12,41 @@ pub enum ErrorKind {
     BlockSizeTooBig,
     FileIsEmpty,
     IntegerOverflow,
+    InvalidBase58,
+    InvalidBase64,
+    InvalidBech32,
     InvalidBlockHash,
     InvalidBlockSize,
+    InvalidChecksum,
     InvalidDataLength,
     InvalidDigestLength,
     InvalidFileSize,
+    InvalidHexString,
     InvalidIndex,
+    InvalidIterationCount,
     InvalidMerkleLeaves,
+    InvalidMultihash,
+    InvalidParameter,
     InvalidSliceLength,
+    InvalidTarget,
+    #[cfg(feature = "std")]
     IOError(std::io::ErrorKind),
-    ParseError(std::num::ParseIntError),
+    OutputTooLong,
+    ParseError(core::num::ParseIntError),
     PathDoesNotExist,
     PathIsNotAFile,
     SliceTooLong,
     SliceTooShort,
     StringTooLong,
     StringTooShort,
+    UnknownAlgorithm,
+    UnsupportedCpuFeature,
     ZeroBlockSize,
 }
 
 impl Display for ErrorKind {
     /// Implementation of the Display trait for sha256::Error.
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use ErrorKind::*;
         match self {
             BadStreamPosition => f.write_str("Bad stream position."),
@@ -41,22 +54,35 @@ impl Display for ErrorKind {
             BlockSizeTooBig => f.write_str("Block size is to big."),
             FileIsEmpty => f.write_str("File is empty."),
             IntegerOverflow => f.write_str("Integer overflow."),
+            InvalidBase58 => f.write_str("Invalid Base58 string."),
+            InvalidBase64 => f.write_str("Invalid Base64 string."),
+            InvalidBech32 => f.write_str("Invalid Bech32 string."),
             InvalidBlockHash => f.write_str("Invalid block hash."),
             InvalidBlockSize => f.write_str("Invalid block size."),
+            InvalidChecksum => f.write_str("Invalid checksum."),
             InvalidDataLength =>  f.write_str("Invalid data length."),
             InvalidDigestLength => f.write_str("Invalid digest length."),
             InvalidFileSize => f.write_str("Invalid file size."),
+            InvalidHexString => f.write_str("Invalid hexidecimal string."),
             InvalidIndex => f.write_str("Invalid index (out of bounds)."),
+            InvalidIterationCount => f.write_str("Invalid iteration count."),
             InvalidMerkleLeaves => f.write_str("Invalid merkle tree leaves."),
+            InvalidMultihash => f.write_str("Invalid multihash."),
+            InvalidParameter => f.write_str("Invalid parameter."),
             InvalidSliceLength => f.write_str("Invalid slice length."),
-            IOError(e) => f.write_str(&e.to_string()),
-            ParseError(e) => f.write_str(&e.to_string()),
+            InvalidTarget => f.write_str("Invalid proof of work target."),
+            #[cfg(feature = "std")]
+            IOError(e) => e.fmt(f),
+            OutputTooLong => f.write_str("Requested output is too long."),
+            ParseError(e) => e.fmt(f),
             PathDoesNotExist => f.write_str("Path does not exist."),
             PathIsNotAFile => f.write_str("Path is not a file."),
             SliceTooLong => f.write_str("Slice too long."),
             SliceTooShort => f.write_str("Slice too short."),
             StringTooLong => f.write_str("String too long."),
             StringTooShort => f.write_str("String to short."),
+            UnknownAlgorithm => f.write_str("Unknown hash algorithm."),
+            UnsupportedCpuFeature => f.write_str("Unsupported CPU feature."),
             ZeroBlockSize => f.write_str("Zero block size."),
         }
     }
@@ -75,18 +101,19 @@ impl Error {
 }
 
 impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_fmt(format_args!("{} {}", self.kind, self.message))
     }
 }
 
-impl From<std::num::ParseIntError> for Error {
-    /// Converts from a std::num::ParseIntError to a sha256::Error.
-    fn from(e: std::num::ParseIntError) -> Self {
+impl From<core::num::ParseIntError> for Error {
+    /// Converts from a core::num::ParseIntError to a sha256::Error.
+    fn from(e: core::num::ParseIntError) -> Self {
         Error::new(ErrorKind::ParseError(e), "Parse integer error.")
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
     /// Converts from a std::io::Error to a sha256::Error.
     fn from(e: std::io::Error) -> Self {
@@ -95,9 +122,9 @@ impl From<std::io::Error> for Error {
 }
 
 /// Implementation of the standard Error trait for sha256::Error
-impl std::error::Error for Error {}
+impl core::error::Error for Error {}
 
 /// A type used to standardize the result type used throughout bc_hash. This simplifies the Result<> return
 /// types throughout the library and helps ensure the consistent use of Self::Error, which can be easily
 /// used with other error types in the standard library.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
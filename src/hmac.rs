@@ -0,0 +1,176 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::digest::Digest;
+use crate::sha2::{Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+use crate::sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use crate::OneWayHasher;
+
+// https://www.rfc-editor.org/rfc/rfc2104
+// https://www.rfc-editor.org/rfc/rfc4231
+// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.198-1.pdf
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// The names of the HMACs built on the hashers that have a type alias below.
+const NAMES: [(&str, &str); 10] = [
+    ("sha224", "hmac-sha224"),
+    ("sha256", "hmac-sha256"),
+    ("sha384", "hmac-sha384"),
+    ("sha512", "hmac-sha512"),
+    ("sha512/224", "hmac-sha512/224"),
+    ("sha512/256", "hmac-sha512/256"),
+    ("sha3-224", "hmac-sha3-224"),
+    ("sha3-256", "hmac-sha3-256"),
+    ("sha3-384", "hmac-sha3-384"),
+    ("sha3-512", "hmac-sha3-512"),
+];
+
+/// Returns the name of the HMAC built on the hasher named ```name```, or ```"hmac"``` if it is not
+/// in ```NAMES```. Strings cannot be compared with ```==``` in a const context, hence the loops.
+const fn hmac_name(name: &str) -> &'static str {
+    let name: &[u8] = name.as_bytes();
+    let mut i: usize = 0;
+    while i < NAMES.len() {
+        let candidate: &[u8] = NAMES[i].0.as_bytes();
+        if candidate.len() == name.len() {
+            let mut j: usize = 0;
+            while j < name.len() && candidate[j] == name[j] {
+                j += 1;
+            }
+            if j == name.len() {
+                return NAMES[i].1;
+            }
+        }
+        i += 1;
+    }
+    "hmac"
+}
+
+/// A keyed-hash message authentication code (HMAC) built on top of any ```OneWayHasher```.
+/// ```MDLEN``` is the digest length of ```H``` and ```BLOCKLEN``` is its block length in bytes
+/// (or its rate in the case of SHA-3), which must equal ```H::BLOCK_SIZE```.
+pub struct Hmac<H, const MDLEN: usize, const BLOCKLEN: usize>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    inner: H,
+    outer: H,
+    ipad: [u8; BLOCKLEN],
+    opad: [u8; BLOCKLEN],
+}
+
+impl<H, const MDLEN: usize, const BLOCKLEN: usize> Hmac<H, MDLEN, BLOCKLEN>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    /// Returns a new HMAC context for ```key```. Keys longer than ```BLOCKLEN``` are hashed first,
+    /// as required by RFC 2104. Fails to compile if ```BLOCKLEN``` is not ```H::BLOCK_SIZE``` or
+    /// is less than ```MDLEN```.
+    pub fn new(key: &[u8]) -> Self {
+        const { assert!(BLOCKLEN == H::BLOCK_SIZE, "BLOCKLEN must equal the block size of H") };
+        const { assert!(BLOCKLEN >= MDLEN, "BLOCKLEN must not be less than MDLEN") };
+        let mut block: [u8; BLOCKLEN] = [0; BLOCKLEN];
+        if key.len() > BLOCKLEN {
+            block[..MDLEN].clone_from_slice(&H::init().update(key).finish().0);
+        } else {
+            block[..key.len()].clone_from_slice(key);
+        }
+        let mut ipad: [u8; BLOCKLEN] = [IPAD; BLOCKLEN];
+        let mut opad: [u8; BLOCKLEN] = [OPAD; BLOCKLEN];
+        for ((i, o), k) in ipad.iter_mut().zip(opad.iter_mut()).zip(block.iter()) {
+            *i ^= k;
+            *o ^= k;
+        }
+        let mut hmac: Self = Self {
+            inner: H::init(),
+            outer: H::init(),
+            ipad,
+            opad,
+        };
+        hmac.reset();
+        hmac
+    }
+
+    /// Calculates the tag and compares it to ```tag``` in constant time. Returns true if they are
+    /// equal. Tags whose length is not equal to ```MDLEN``` are always rejected.
+    pub fn verify(&mut self, tag: &[u8]) -> bool {
+        let expected: Digest<MDLEN> = self.finish();
+        expected.ct_eq(tag)
+    }
+}
+
+/// Wipes the padded keys. The inner and outer hashers wipe themselves.
+#[cfg(feature = "zeroize")]
+impl<H, const MDLEN: usize, const BLOCKLEN: usize> Drop for Hmac<H, MDLEN, BLOCKLEN>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.ipad.zeroize();
+        self.opad.zeroize();
+    }
+}
+
+impl<H, const MDLEN: usize, const BLOCKLEN: usize> OneWayHasher<MDLEN> for Hmac<H, MDLEN, BLOCKLEN>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    const NAME: &'static str = hmac_name(H::NAME);
+    const BLOCK_SIZE: usize = BLOCKLEN;
+
+    /// Returns an HMAC context with an empty key. Use ```Hmac::new()``` to supply a key.
+    #[inline]
+    fn init() -> Self {
+        Self::new(&[])
+    }
+
+    /// Resets the context to its initial state while keeping the key.
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.inner.reset().update(&self.ipad);
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.inner.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
+        let mut inner_digest: [u8; MDLEN] = [0; MDLEN];
+        self.inner.finish_into(&mut inner_digest);
+        self.outer.reset().update(&self.opad).update(&inner_digest).finish_into(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H, const MDLEN: usize, const BLOCKLEN: usize> std::io::Write for Hmac<H, MDLEN, BLOCKLEN>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+pub type HmacSha224 = Hmac<Sha224, 28, 64>;
+pub type HmacSha256 = Hmac<Sha256, 32, 64>;
+pub type HmacSha384 = Hmac<Sha384, 48, 128>;
+pub type HmacSha512 = Hmac<Sha512, 64, 128>;
+pub type HmacSha512_224 = Hmac<Sha512_224, 28, 128>;
+pub type HmacSha512_256 = Hmac<Sha512_256, 32, 128>;
+pub type HmacSha3_224 = Hmac<Sha3_224, 28, 144>;
+pub type HmacSha3_256 = Hmac<Sha3_256, 32, 136>;
+pub type HmacSha3_384 = Hmac<Sha3_384, 48, 104>;
+pub type HmacSha3_512 = Hmac<Sha3_512, 64, 72>;
@@ -0,0 +1,124 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::hmac::Hmac;
+use crate::sha2::{Sha256, Sha512};
+use crate::OneWayHasher;
+use core::marker::PhantomData;
+
+// https://www.rfc-editor.org/rfc/rfc8018#section-5.2
+// https://www.rfc-editor.org/rfc/rfc5869
+// https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki#from-mnemonic-to-seed
+
+/// Derives a key from ```password``` and ```salt``` using PBKDF2 with HMAC-```H``` as the
+/// pseudorandom function, filling all of ```output```. ```MDLEN``` and ```BLOCKLEN``` are the
+/// digest and block lengths of ```H```, and the latter is checked against ```H::BLOCK_SIZE``` at
+/// compile time. For example, a BIP39 seed is derived with
+/// ```pbkdf2::<Sha512, 64, 128>(mnemonic, b"mnemonic", 2048, &mut seed)```.
+pub fn pbkdf2<H, const MDLEN: usize, const BLOCKLEN: usize>(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    output: &mut [u8],
+) -> Result<()>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    if iterations == 0 {
+        Err(Error::new(ErrorKind::InvalidIterationCount, "The iteration count must be greater than zero."))
+    } else if output.len() as u64 > u32::MAX as u64 * MDLEN as u64 {
+        Err(Error::new(ErrorKind::OutputTooLong, "The derived key is longer than (2^32 - 1) * MDLEN."))
+    } else {
+        let mut prf: Hmac<H, MDLEN, BLOCKLEN> = Hmac::new(password);
+        let mut u: [u8; MDLEN] = [0; MDLEN];
+        let mut t: [u8; MDLEN] = [0; MDLEN];
+        for (i, block) in output.chunks_mut(MDLEN).enumerate() {
+            // U_1 = PRF(P, S || INT(i))
+            prf.reset()
+                .update(salt)
+                .update(&(i as u32 + 1).to_be_bytes())
+                .finish_into(&mut u);
+            t.copy_from_slice(&u);
+            // U_c = PRF(P, U_{c-1})
+            for _ in 1..iterations {
+                prf.reset().update(&u).finish_into(&mut u);
+                for (a, b) in t.iter_mut().zip(u.iter()) {
+                    *a ^= b;
+                }
+            }
+            block.clone_from_slice(&t[..block.len()]);
+        }
+        Ok(())
+    }
+}
+
+/// The HMAC-based extract-and-expand key derivation function described in RFC 5869. ```MDLEN```
+/// and ```BLOCKLEN``` are the digest and block lengths of ```H```, as for ```Hmac```.
+pub struct Hkdf<H, const MDLEN: usize, const BLOCKLEN: usize>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    prk: [u8; MDLEN],
+    _h: PhantomData<H>,
+}
+
+impl<H, const MDLEN: usize, const BLOCKLEN: usize> Hkdf<H, MDLEN, BLOCKLEN>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    /// Performs the extract step, concentrating the entropy of the input keying material ```ikm```
+    /// into a pseudorandom key. An empty ```salt``` is treated as ```MDLEN``` zero bytes.
+    pub fn extract(salt: &[u8], ikm: &[u8]) -> Self {
+        Self::from_prk(Hmac::<H, MDLEN, BLOCKLEN>::new(salt).update(ikm).finish().0)
+    }
+
+    /// Returns a new context from an existing pseudorandom key, skipping the extract step.
+    pub fn from_prk(prk: [u8; MDLEN]) -> Self {
+        Self {
+            prk,
+            _h: PhantomData,
+        }
+    }
+
+    /// Returns the pseudorandom key produced by the extract step.
+    pub fn prk(&self) -> &[u8; MDLEN] {
+        &self.prk
+    }
+
+    /// Performs the expand step, filling ```okm``` with output keying material bound to the
+    /// context string ```info```. The output may be up to 255 * ```MDLEN``` bytes long.
+    pub fn expand(&self, info: &[u8], okm: &mut [u8]) -> Result<()> {
+        if okm.len() > 255 * MDLEN {
+            Err(Error::new(ErrorKind::OutputTooLong, "The output keying material is longer than 255 * MDLEN."))
+        } else {
+            let mut prf: Hmac<H, MDLEN, BLOCKLEN> = Hmac::new(&self.prk);
+            let mut t: [u8; MDLEN] = [0; MDLEN];
+            for (i, block) in okm.chunks_mut(MDLEN).enumerate() {
+                // T(i) = HMAC-Hash(PRK, T(i-1) | info | i)
+                prf.reset();
+                if i > 0 {
+                    prf.update(&t);
+                }
+                prf.update(info).update(&[i as u8 + 1]).finish_into(&mut t);
+                block.clone_from_slice(&t[..block.len()]);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Wipes the pseudorandom key.
+#[cfg(feature = "zeroize")]
+impl<H, const MDLEN: usize, const BLOCKLEN: usize> Drop for Hkdf<H, MDLEN, BLOCKLEN>
+where
+    H: OneWayHasher<MDLEN>,
+{
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.prk);
+    }
+}
+
+pub type HkdfSha256 = Hkdf<Sha256, 32, 64>;
+pub type HkdfSha512 = Hkdf<Sha512, 64, 128>;
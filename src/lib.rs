@@ -2,26 +2,159 @@
 // Distributed under the MIT software license, see the accompanying
 // file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Detects a CPU feature at runtime. Runtime detection needs std, so without it only the features
+/// enabled at compile time (e.g. with ```-C target-cpu=native```) are used.
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+macro_rules! cpu_feature {
+    ($feature:tt) => {
+        std::is_x86_feature_detected!($feature)
+    };
+}
+
+/// Without std, reports whether the feature was enabled at compile time.
+#[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+macro_rules! cpu_feature {
+    ($feature:tt) => {{
+        const ENABLED: bool = cfg!(target_feature = $feature);
+        ENABLED
+    }};
+}
+
+pub mod blake2;
+#[cfg(feature = "alloc")]
+pub mod blake3;
+#[cfg(feature = "std")]
 pub mod cache;
+pub mod difficulty;
 pub mod digest;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
+#[cfg(feature = "alloc")]
+pub mod encoding;
 pub mod error;
+pub mod hmac;
+#[cfg(feature = "std")]
+#[allow(clippy::io_other_error, clippy::seek_from_current, clippy::unnecessary_cast, clippy::useless_conversion)]
 pub mod io;
+pub mod kdf;
 pub mod merkle;
+pub mod multibuffer;
+#[cfg(feature = "alloc")]
+pub mod multihash;
+pub mod pow;
+pub mod ripemd;
 pub mod sha2;
 pub mod sha3;
+#[cfg(target_arch = "x86_64")]
+mod sha_ni;
+#[cfg(feature = "alloc")]
+pub mod sp800_185;
+pub mod tagged;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+use digest::Digest;
 use error::Result;
+#[cfg(feature = "alloc")]
 use merkle::Proof;
-use std::ops::Range;
 
-pub trait OneWayHasher<const MDLEN: usize>: std::io::Write
+/// Overwrites the memory of ```value``` with zeros in a way that the compiler cannot optimize
+/// away, so that secret material does not linger after a context is reset or dropped.
+///
+/// # Safety
+/// ```T``` must be plain data for which all zero bytes is a valid value, e.g. integer arrays or
+/// unions of them.
+#[cfg(feature = "zeroize")]
+pub(crate) unsafe fn wipe<T>(value: &mut T) {
+    use zeroize::Zeroize;
+    core::slice::from_raw_parts_mut(value as *mut T as *mut u8, core::mem::size_of::<T>()).zeroize();
+}
+
+/// ```std::io::Write``` when the ```std``` feature is enabled, and nothing otherwise. Every hasher
+/// can be used as a writer on hosted targets, while the core hashing code still builds without std.
+#[cfg(feature = "std")]
+pub trait MaybeWrite: std::io::Write {}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Write> MaybeWrite for T {}
+
+/// Without std there is no ```std::io::Write```, so this is implemented for every type.
+#[cfg(not(feature = "std"))]
+pub trait MaybeWrite {}
+
+#[cfg(not(feature = "std"))]
+impl<T> MaybeWrite for T {}
+
+pub trait OneWayHasher<const MDLEN: usize>: MaybeWrite
 where
     Self: Sized,
 {
+    /// The name of the algorithm, e.g. ```"sha256"``` or ```"sha3-256"```.
+    const NAME: &'static str;
+
+    /// The number of bytes processed at a time, i.e. the block size (or the rate for SHA-3).
+    const BLOCK_SIZE: usize;
+
     fn init() -> Self;
     fn reset(&mut self) -> &mut Self;
     fn update(&mut self, data: &[u8]) -> &mut Self;
-    //fn finish(&mut self, digest: &mut [u8; MDLEN]);
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()>;
+
+    /// Finalizes the hash and writes the digest to ```digest```. Call ```reset()``` before using
+    /// the hasher again.
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]);
+
+    /// Finalizes the hash and returns the digest. Call ```reset()``` before using the hasher again.
+    #[inline]
+    fn finish(&mut self) -> Digest<MDLEN> {
+        let mut digest: Digest<MDLEN> = Digest::new();
+        self.finish_into(&mut digest.0);
+        digest
+    }
+
+    /// Returns the digest of ```data```, which may be anything that can be viewed as bytes, such
+    /// as a ```String```, ```&str```, ```Vec<u8>``` or array.
+    #[inline]
+    fn digest<T: AsRef<[u8]>>(data: T) -> Digest<MDLEN> {
+        Self::init().update(data.as_ref()).finish()
+    }
+
+    /// Returns the digest of everything read from ```reader``` until the end of the stream. The
+    /// data is streamed through the hasher's ```std::io::Write``` implementation in small chunks,
+    /// so it never has to fit in memory.
+    #[cfg(feature = "std")]
+    fn digest_reader<R: std::io::Read>(mut reader: R) -> Result<Digest<MDLEN>> {
+        let mut hasher: Self = Self::init();
+        std::io::copy(&mut reader, &mut hasher)?;
+        Ok(hasher.finish())
+    }
+
+    /// Returns the digest of the contents of the file at ```path```.
+    #[cfg(feature = "std")]
+    fn digest_file(path: &std::path::Path) -> Result<Digest<MDLEN>> {
+        if !path.exists() {
+            Err(error::Error::new(error::ErrorKind::PathDoesNotExist, "The path does not exist."))
+        } else if !path.is_file() {
+            Err(error::Error::new(error::ErrorKind::PathIsNotAFile, "The path is not a file."))
+        } else {
+            Self::digest_reader(std::io::BufReader::new(std::fs::File::open(path)?))
+        }
+    }
+
+    /// Replaces the first half of ```nodes``` with the hashes of each adjacent pair of nodes, so
+    /// that ```nodes[i] = H(nodes[2i] || nodes[2i + 1])```. The length of ```nodes``` must be even.
+    /// The merkle functions call this once for each level of the tree, which lets hashers that can
+    /// process several messages at once override it.
+    fn hash_pairs(&mut self, nodes: &mut [[u8; MDLEN]]) -> Result<()> {
+        for i in 0..(nodes.len() / 2) {
+            let (left, right) = (nodes[i * 2], nodes[i * 2 + 1]);
+            self.reset().update(&left).update(&right).finish_into(&mut nodes[i]);
+        }
+        Ok(())
+    }
 }
 
 pub trait FinishXOF
@@ -60,6 +193,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 pub trait BlockChainDB<const DIGEST_SIZE: usize, const BLOCK_SIZE: usize, H, T>
 where
     Self: Default + Sized,
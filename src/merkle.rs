@@ -2,13 +2,20 @@
 // Distributed under the MIT software license, see the accompanying
 // file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
 
+#[cfg(feature = "alloc")]
+use crate::digest::ct_eq;
+#[cfg(feature = "alloc")]
 use crate::error;
+#[cfg(feature = "alloc")]
 use crate::OneWayHasher;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Calculates the merkle root for a vector of leaves where each leaf is the hash digest of
 /// a record in a block of data. This function reduces the ```leaves``` argument down to a
 /// single element in the vector, which is the root. It returns a ```bool``` value indicating
 /// whether or not a mutation was encountered during the calculation.
+#[cfg(feature = "alloc")]
 pub fn compute_root<const MDLEN: usize, H>(leaves: &mut Vec<[u8; MDLEN]>) -> error::Result<bool>
 where
     H: OneWayHasher<MDLEN>,
@@ -26,28 +33,30 @@ where
             // the vector contains an odd number of leaves, so copy and append the last leaf to make it an even number.
             match leaves.last() {
                 Some(d) => leaves.push(*d),
-                None => return Err(error::Error::InvalidMerkleLeaves), // should never get here
+                None => return Err(error::Error::new(error::ErrorKind::InvalidMerkleLeaves, "The vector of leaves is empty.")), // should never get here
             }
         }
-        for i in 0..(leaves.len() / 2) {
-            hasher
-                .reset()
-                .update(&leaves[i * 2])
-                .update(&leaves[i * 2 + 1])
-                .finish(&mut leaves[i]);
-        }
+        hasher.hash_pairs(leaves)?;
         leaves.truncate(leaves.len() / 2)
     }
     Ok(mutation)
 }
 
+/// A sibling on the path from a leaf to the root, which is hashed on the left or right of the
+/// running digest. With the ```serde``` feature a node serializes as ```{"left": "<hex>"}``` or
+/// ```{"right": "<hex>"}``` in human-readable formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum ChildNode<const MDLEN: usize> {
-    Left([u8; MDLEN]),
-    Right([u8; MDLEN]),
+    Left(#[cfg_attr(feature = "serde", serde(with = "crate::digest::hex_or_bytes"))] [u8; MDLEN]),
+    Right(#[cfg_attr(feature = "serde", serde(with = "crate::digest::hex_or_bytes"))] [u8; MDLEN]),
 }
 
+#[cfg(feature = "alloc")]
 pub type Proof<const MDLEN: usize> = Vec<ChildNode<MDLEN>>;
 
+#[cfg(feature = "alloc")]
 pub fn compute_proof<const MDLEN: usize, H>(
     leaves: &mut Vec<[u8; MDLEN]>,
     mut index: usize,
@@ -59,7 +68,7 @@ where
     let mut proof: Proof<MDLEN> = Proof::new();
     let mut hasher: H = H::init();
     if index >= leaves.len() {
-        Err(error::Error::InvalidIndex)
+        Err(error::Error::new(error::ErrorKind::InvalidIndex, "The index is not less than the number of leaves."))
     } else {
         while leaves.len() > 1 {
             for i in 0..leaves.len() - 1 {
@@ -70,7 +79,7 @@ where
             if leaves.len() & 1 != 0 {
                 match leaves.last() {
                     Some(d) => leaves.push(*d),
-                    None => return Err(error::Error::InvalidMerkleLeaves),
+                    None => return Err(error::Error::new(error::ErrorKind::InvalidMerkleLeaves, "The vector of leaves is empty.")),
                 }
             }
             proof.push(if index & 1 == 1 {
@@ -78,13 +87,7 @@ where
             } else {
                 ChildNode::Right(leaves[index ^ 1])
             });
-            for i in 0..(leaves.len() / 2) {
-                hasher
-                    .reset()
-                    .update(&leaves[i * 2])
-                    .update(&leaves[i * 2 + 1])
-                    .finish(&mut leaves[i]);
-            }
+            hasher.hash_pairs(leaves)?;
             leaves.truncate(leaves.len() / 2);
             index >>= 1;
         }
@@ -92,6 +95,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 pub fn prove<const MDLEN: usize, H>(proof: Proof<MDLEN>, digest: &mut [u8; MDLEN])
 where
     H: OneWayHasher<MDLEN>,
@@ -104,15 +108,27 @@ where
                     .reset()
                     .update(sibling)
                     .update(&digest[..])
-                    .finish(digest);
+                    .finish_into(digest);
             }
             ChildNode::Right(sibling) => {
                 hasher
                     .reset()
                     .update(&digest[..])
                     .update(sibling)
-                    .finish(digest);
+                    .finish_into(digest);
             }
         }
     }
 }
+
+/// Hashes ```leaf``` up the path in ```proof``` and compares the result to ```root``` in constant
+/// time. Returns true if the proof shows that ```leaf``` is in the tree with that root.
+#[cfg(feature = "alloc")]
+pub fn verify_proof<const MDLEN: usize, H>(proof: Proof<MDLEN>, leaf: &[u8; MDLEN], root: &[u8; MDLEN]) -> bool
+where
+    H: OneWayHasher<MDLEN>,
+{
+    let mut digest: [u8; MDLEN] = *leaf;
+    prove::<MDLEN, H>(proof, &mut digest);
+    ct_eq(&digest, root)
+}
@@ -0,0 +1,232 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::sha2::Sha256;
+use crate::OneWayHasher;
+
+// https://www.intel.com/content/dam/www/public/us/en/documents/white-papers/communications-ia-multi-buffer-paper.pdf
+
+/// Returns the big-endian word at ```pos``` of ```msg``` after SHA-256 padding has been applied,
+/// where ```padded_len``` is the length of the padded message.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn padded_word(msg: &[u8], pos: usize, padded_len: usize) -> u32 {
+    if pos + 4 <= msg.len() {
+        u32::from_be_bytes(msg[pos..(pos + 4)].try_into().unwrap())
+    } else if pos >= padded_len - 8 {
+        // the message length in bits occupies the last 8 bytes
+        (((msg.len() as u64) * 8) >> (8 * (padded_len - 4 - pos))) as u32
+    } else if pos > msg.len() {
+        0
+    } else {
+        let mut word: [u8; 4] = [0; 4];
+        for (i, b) in word.iter_mut().enumerate() {
+            if pos + i < msg.len() {
+                *b = msg[pos + i];
+            } else if pos + i == msg.len() {
+                *b = 0x80;
+            }
+        }
+        u32::from_be_bytes(word)
+    }
+}
+
+/// Defines a function that calculates the SHA-256 digests of several messages at once, one per
+/// lane of a SIMD register. Parameters are as follows:
+///    $name - The name of the function to define
+///    $feature - The target feature(s) the function requires
+///    $lanes - The number of 32 bit lanes in a register
+///    $v - The register type
+///    $load, $store - Unaligned load and store
+///    $set1 - Broadcasts a single value to all lanes
+///    $add, $xor, $and, $andnot, $or - Lane-wise arithmetic and bitwise operations
+///    $srli, $slli - Lane-wise logical shifts by an immediate
+#[cfg(target_arch = "x86_64")]
+macro_rules! multi_lane_sha256 {
+    ($name:ident, $feature:literal, $lanes:literal, $v:ty, $load:ident, $store:ident, $set1:ident, $add:ident, $xor:ident, $and:ident, $andnot:ident, $or:ident, $srli:ident, $slli:ident) => {
+        /// Hashes exactly one message per lane, writing each digest to the same position in ```digests```.
+        #[target_feature(enable = $feature)]
+        unsafe fn $name<const LEN: usize>(messages: &[[u8; LEN]], digests: &mut [[u8; 32]]) {
+            use core::arch::x86_64::*;
+
+            macro_rules! rotr {
+                ($x:expr, $n:literal) => {
+                    $or($srli::<$n>($x), $slli::<{ 32 - $n }>($x))
+                };
+            }
+
+            let padded_len: usize = (LEN + 9).div_ceil(64) * 64;
+            let mut st: [$v; 8] = [$set1(0); 8];
+            for (s, iv) in st.iter_mut().zip(crate::sha2::INITIAL_VALUES_256) {
+                *s = $set1(iv as i32);
+            }
+
+            for pos in (0..padded_len).step_by(64) {
+                // load the chunk of each message into the lanes, transposing it in the process
+                let mut w: [$v; 64] = [$set1(0); 64];
+                for (i, word) in w.iter_mut().take(16).enumerate() {
+                    let mut lanes: [u32; $lanes] = [0; $lanes];
+                    for (lane, msg) in lanes.iter_mut().zip(messages) {
+                        *lane = padded_word(msg, pos + i * 4, padded_len);
+                    }
+                    *word = $load(lanes.as_ptr() as *const $v);
+                }
+
+                // extend the first 16 words into the remaining words of the message schedule
+                for i in 16..64 {
+                    let s0: $v = $xor($xor(rotr!(w[i - 15], 7), rotr!(w[i - 15], 18)), $srli::<3>(w[i - 15]));
+                    let s1: $v = $xor($xor(rotr!(w[i - 2], 17), rotr!(w[i - 2], 19)), $srli::<10>(w[i - 2]));
+                    w[i] = $add($add(w[i - 16], s0), $add(w[i - 7], s1));
+                }
+
+                // the compression loop
+                let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = st;
+                for (wi, k) in w.iter().zip(crate::sha2::CONSTANTS_256) {
+                    let sigma1: $v = $xor($xor(rotr!(e, 6), rotr!(e, 11)), rotr!(e, 25));
+                    let choice: $v = $xor($and(e, f), $andnot(e, g));
+                    let temp1: $v = $add($add(h, sigma1), $add($add(choice, $set1(k as i32)), *wi));
+                    let sigma0: $v = $xor($xor(rotr!(a, 2), rotr!(a, 13)), rotr!(a, 22));
+                    let majority: $v = $xor($xor($and(a, b), $and(a, c)), $and(b, c));
+                    let temp2: $v = $add(sigma0, majority);
+                    h = g;
+                    g = f;
+                    f = e;
+                    e = $add(d, temp1);
+                    d = c;
+                    c = b;
+                    b = a;
+                    a = $add(temp1, temp2);
+                }
+                for (s, x) in st.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+                    *s = $add(*s, x);
+                }
+            }
+
+            // transpose the state back into one digest per lane
+            for (i, s) in st.iter().enumerate() {
+                let mut lanes: [u32; $lanes] = [0; $lanes];
+                $store(lanes.as_mut_ptr() as *mut $v, *s);
+                for (digest, lane) in digests.iter_mut().zip(lanes) {
+                    digest[(i * 4)..(i * 4 + 4)].copy_from_slice(&lane.to_be_bytes());
+                }
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+multi_lane_sha256!(
+    sha256_x4, "sse2,sse4.1", 4, __m128i, _mm_loadu_si128, _mm_storeu_si128, _mm_set1_epi32, _mm_add_epi32,
+    _mm_xor_si128, _mm_and_si128, _mm_andnot_si128, _mm_or_si128, _mm_srli_epi32, _mm_slli_epi32
+);
+
+#[cfg(target_arch = "x86_64")]
+multi_lane_sha256!(
+    sha256_x8, "avx2", 8, __m256i, _mm256_loadu_si256, _mm256_storeu_si256, _mm256_set1_epi32, _mm256_add_epi32,
+    _mm256_xor_si256, _mm256_and_si256, _mm256_andnot_si256, _mm256_or_si256, _mm256_srli_epi32, _mm256_slli_epi32
+);
+
+/// Returns the number of messages ```sha256_many()``` hashes at once on this CPU. This is 8 with
+/// AVX2, 4 with SSE4.1 and 1 otherwise. CPUs with the SHA extensions hash one message at a time,
+/// since a single SHA-NI stream is faster than eight AVX2 lanes. The "sha256 4096 x 64B" group in
+/// ```benches/sha256.rs``` compares every lane count the CPU supports, and on a CPU with all three
+/// SHA-NI hashes about 1.5 times as many messages per second as AVX2 and 3 times as many as SSE4.1.
+pub fn lanes() -> usize {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if crate::sha_ni::is_available() {
+            return 1;
+        }
+        if cpu_feature!("avx2") {
+            return 8;
+        }
+        if cpu_feature!("sse4.1") {
+            return 4;
+        }
+    }
+    1
+}
+
+/// Hashes as many whole batches of ```lanes``` messages as there are with the SIMD function for
+/// that number of lanes, and returns the number of messages hashed. Nothing is hashed if there is
+/// no such function. The CPU must support the instructions the function needs.
+#[cfg(target_arch = "x86_64")]
+unsafe fn sha256_batches<const LEN: usize>(lanes: usize, messages: &[[u8; LEN]], digests: &mut [[u8; 32]]) -> usize {
+    let mut done: usize = 0;
+    for (m, d) in messages.chunks_exact(lanes).zip(digests.chunks_exact_mut(lanes)) {
+        match lanes {
+            8 => sha256_x8(m, d),
+            4 => sha256_x4(m, d),
+            _ => break,
+        }
+        done += lanes;
+    }
+    done
+}
+
+/// Calculates the SHA-256 digest of each message in ```messages```, writing it to the digest at
+/// the same position in ```digests```. Messages are hashed several at a time using AVX2 or SSE4.1
+/// when the CPU supports them, and one at a time with ```Sha256``` otherwise. See ```lanes()```.
+pub fn sha256_many<const LEN: usize>(messages: &[[u8; LEN]], digests: &mut [[u8; 32]]) -> Result<()> {
+    sha256_many_lanes(lanes(), messages, digests)
+}
+
+/// The same as ```sha256_many()```, except that messages are hashed ```lanes``` at a time instead
+/// of however many ```lanes()``` returns. ```lanes``` must be 8 (AVX2), 4 (SSE4.1) or 1, and fails
+/// if the CPU does not support the instructions needed.
+pub fn sha256_many_lanes<const LEN: usize>(lanes: usize, messages: &[[u8; LEN]], digests: &mut [[u8; 32]]) -> Result<()> {
+    if messages.len() != digests.len() {
+        return Err(Error::new(ErrorKind::InvalidSliceLength, "The number of digests is not equal to the number of messages."));
+    }
+    let done: usize = match lanes {
+        1 => 0,
+        #[cfg(target_arch = "x86_64")]
+        8 if cpu_feature!("avx2") => unsafe { sha256_batches(8, messages, digests) },
+        #[cfg(target_arch = "x86_64")]
+        4 if cpu_feature!("sse4.1") => unsafe { sha256_batches(4, messages, digests) },
+        _ => return Err(Error::new(ErrorKind::UnsupportedCpuFeature, "The CPU cannot hash that many messages at once.")),
+    };
+    let mut ctx: Sha256 = Sha256::init();
+    for (m, d) in messages[done..].iter().zip(digests[done..].iter_mut()) {
+        ctx.reset().update(m).finish_into(d);
+    }
+    Ok(())
+}
+
+/// Calculates the double SHA-256 digest, i.e. SHA256(SHA256(x)), of each message in ```messages```,
+/// writing it to the digest at the same position in ```digests```. See ```sha256_many()```.
+pub fn sha256d_many<const LEN: usize>(messages: &[[u8; LEN]], digests: &mut [[u8; 32]]) -> Result<()> {
+    sha256_many(messages, digests)?;
+    let mut first: [[u8; 32]; 8] = [[0; 32]; 8];
+    for batch in digests.chunks_mut(8) {
+        first[..batch.len()].copy_from_slice(batch);
+        sha256_many(&first[..batch.len()], batch)?;
+    }
+    Ok(())
+}
+
+/// The signature shared by ```sha256_many::<64>()``` and ```sha256d_many::<64>()```.
+type HashPairsFn = fn(&[[u8; 64]], &mut [[u8; 32]]) -> Result<()>;
+
+/// Replaces the first half of ```nodes``` with the hashes of each adjacent pair of nodes, where
+/// ```hash``` is either ```sha256_many()``` or ```sha256d_many()```. Used to hash one level of a
+/// merkle tree at a time.
+pub(crate) fn hash_pairs(nodes: &mut [[u8; 32]], hash: HashPairsFn) -> Result<()> {
+    let mut pairs: [[u8; 64]; 8] = [[0; 64]; 8];
+    let mut parents: [[u8; 32]; 8] = [[0; 32]; 8];
+    let mut i: usize = 0;
+    while i < nodes.len() / 2 {
+        // parents are only ever written behind the children that are still to be read
+        let n: usize = (nodes.len() / 2 - i).min(8);
+        for (j, pair) in pairs[..n].iter_mut().enumerate() {
+            pair[..32].copy_from_slice(&nodes[(i + j) * 2]);
+            pair[32..].copy_from_slice(&nodes[(i + j) * 2 + 1]);
+        }
+        hash(&pairs[..n], &mut parents[..n])?;
+        nodes[i..(i + n)].copy_from_slice(&parents[..n]);
+        i += n;
+    }
+    Ok(())
+}
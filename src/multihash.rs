@@ -0,0 +1,120 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::dynamic::Algorithm;
+use crate::error::{Error, ErrorKind, Result};
+use alloc::vec::Vec;
+
+// https://github.com/multiformats/multihash
+// https://github.com/multiformats/multicodec/blob/master/table.csv
+// https://github.com/multiformats/unsigned-varint
+
+/// The multicodec code of each algorithm that has one. ```Hash160``` does not.
+const CODES: [(Algorithm, u64); 22] = [
+    (Algorithm::Sha224, 0x1013),
+    (Algorithm::Sha256, 0x12),
+    (Algorithm::Sha384, 0x20),
+    (Algorithm::Sha512, 0x13),
+    (Algorithm::Sha512_224, 0x1014),
+    (Algorithm::Sha512_256, 0x1015),
+    (Algorithm::Sha256d, 0x56),
+    (Algorithm::Sha3_224, 0x17),
+    (Algorithm::Sha3_256, 0x16),
+    (Algorithm::Sha3_384, 0x15),
+    (Algorithm::Sha3_512, 0x14),
+    (Algorithm::Keccak224, 0x1a),
+    (Algorithm::Keccak256, 0x1b),
+    (Algorithm::Keccak384, 0x1c),
+    (Algorithm::Keccak512, 0x1d),
+    (Algorithm::Shake128, 0x18),
+    (Algorithm::Shake256, 0x19),
+    (Algorithm::Blake2b256, 0xb220),
+    (Algorithm::Blake2b512, 0xb240),
+    (Algorithm::Blake2s256, 0xb260),
+    (Algorithm::Blake3, 0x1e),
+    (Algorithm::Ripemd160, 0x1053),
+];
+
+/// The maximum number of bytes in an unsigned varint, as set by the multiformats spec.
+const MAX_VARINT_LEN: usize = 9;
+
+impl Algorithm {
+    /// Returns the multicodec code of the algorithm, or ```None``` if it does not have one.
+    pub fn multihash_code(&self) -> Option<u64> {
+        CODES.iter().find(|(a, _)| a == self).map(|(_, code)| *code)
+    }
+
+    /// Returns the algorithm with the multicodec code ```code```.
+    pub fn from_multihash_code(code: u64) -> Result<Self> {
+        CODES
+            .iter()
+            .find(|(_, c)| *c == code)
+            .map(|(a, _)| *a)
+            .ok_or(Error::new(ErrorKind::UnknownAlgorithm, "There is no hash algorithm with that multihash code."))
+    }
+}
+
+/// Appends ```value``` to ```buf``` as an unsigned LEB128 varint.
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Reads an unsigned varint from the front of ```bytes```, returning the value and the rest of
+/// ```bytes```. Encodings that are longer than necessary are rejected.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    let mut value: u64 = 0;
+    for (i, b) in bytes.iter().take(MAX_VARINT_LEN).enumerate() {
+        value |= ((b & 0x7f) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            if *b == 0 && i > 0 {
+                return Err(Error::new(ErrorKind::InvalidMultihash, "The varint is not minimally encoded."));
+            }
+            return Ok((value, &bytes[(i + 1)..]));
+        }
+    }
+    Err(Error::new(ErrorKind::InvalidMultihash, "The varint is truncated or too long."))
+}
+
+/// Encodes ```digest``` as a multihash, i.e. the code of ```algorithm```, the length of the
+/// digest, and the digest itself. Fails if the algorithm does not have a multicodec code or if
+/// the length of ```digest``` is not its output size.
+pub fn encode(algorithm: Algorithm, digest: &[u8]) -> Result<Vec<u8>> {
+    let code: u64 = algorithm
+        .multihash_code()
+        .ok_or(Error::new(ErrorKind::UnknownAlgorithm, "The hash algorithm does not have a multihash code."))?;
+    if digest.len() != algorithm.output_size() {
+        return Err(Error::new(ErrorKind::InvalidDigestLength, "The digest length does not match the hash algorithm."));
+    }
+    let mut buf: Vec<u8> = Vec::with_capacity(2 * MAX_VARINT_LEN + digest.len());
+    write_varint(&mut buf, code);
+    write_varint(&mut buf, digest.len() as u64);
+    buf.extend_from_slice(digest);
+    Ok(buf)
+}
+
+/// Decodes a multihash, returning the algorithm and the digest. The declared length must be the
+/// output size of the algorithm and must match the number of bytes that follow it.
+pub fn decode(bytes: &[u8]) -> Result<(Algorithm, &[u8])> {
+    let (code, rest) = read_varint(bytes)?;
+    let algorithm: Algorithm = Algorithm::from_multihash_code(code)?;
+    let (len, digest) = read_varint(rest)?;
+    if len != algorithm.output_size() as u64 {
+        Err(Error::new(ErrorKind::InvalidDigestLength, "The declared length does not match the hash algorithm."))
+    } else if digest.len() != algorithm.output_size() {
+        Err(Error::new(ErrorKind::InvalidMultihash, "The number of digest bytes does not match the declared length."))
+    } else {
+        Ok((algorithm, digest))
+    }
+}
+
+/// Hashes ```data``` with the algorithm whose multicodec code is ```code``` and returns the
+/// multihash of the digest.
+pub fn digest(code: u64, data: &[u8]) -> Result<Vec<u8>> {
+    let algorithm: Algorithm = Algorithm::from_multihash_code(code)?;
+    encode(algorithm, &algorithm.digest(data))
+}
@@ -0,0 +1,252 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::digest::Digest;
+use crate::error::{Error, ErrorKind, Result};
+use core::cmp::Ordering;
+
+// https://developer.bitcoin.org/reference/block_chain.html#target-nbits
+// https://github.com/bitcoin/bitcoin/blob/master/src/arith_uint256.cpp
+
+/// An unsigned 256 bit integer stored as four 64 bit limbs, least significant first. This is
+/// only as much arithmetic as is needed for proof of work targets and difficulty adjustment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct U256(pub(crate) [u64; 4]);
+
+impl U256 {
+    pub(crate) const ZERO: U256 = U256([0; 4]);
+    pub(crate) const ONE: U256 = U256([1, 0, 0, 0]);
+
+    pub(crate) fn from_u64(n: u64) -> Self {
+        U256([n, 0, 0, 0])
+    }
+
+    pub(crate) fn from_le_bytes(bytes: &[u8; 32]) -> Self {
+        let mut n: U256 = U256::ZERO;
+        for (limb, chunk) in n.0.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        n
+    }
+
+    pub(crate) fn to_le_bytes(self) -> [u8; 32] {
+        let mut bytes: [u8; 32] = [0; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    /// Returns the number of bits needed to represent the integer, i.e. 0 for zero.
+    pub(crate) fn bits(&self) -> u32 {
+        for i in (0..4).rev() {
+            if self.0[i] != 0 {
+                return 64 * i as u32 + 64 - self.0[i].leading_zeros();
+            }
+        }
+        0
+    }
+
+    /// Returns the low 64 bits.
+    pub(crate) fn low_u64(&self) -> u64 {
+        self.0[0]
+    }
+
+    pub(crate) fn shl(self, shift: u32) -> Self {
+        let mut n: U256 = U256::ZERO;
+        let (limbs, bits) = ((shift / 64) as usize, shift % 64);
+        for i in limbs..4 {
+            n.0[i] = self.0[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                n.0[i] |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        n
+    }
+
+    pub(crate) fn shr(self, shift: u32) -> Self {
+        let mut n: U256 = U256::ZERO;
+        let (limbs, bits) = ((shift / 64) as usize, shift % 64);
+        for i in 0..4usize.saturating_sub(limbs) {
+            n.0[i] = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < 4 {
+                n.0[i] |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        n
+    }
+
+    pub(crate) fn not(self) -> Self {
+        U256(self.0.map(|limb| !limb))
+    }
+
+    /// Returns the sum and whether it overflowed.
+    pub(crate) fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let mut n: U256 = U256::ZERO;
+        let mut carry: bool = false;
+        for i in 0..4 {
+            let (s, c1) = self.0[i].overflowing_add(other.0[i]);
+            let (s, c2) = s.overflowing_add(carry as u64);
+            n.0[i] = s;
+            carry = c1 || c2;
+        }
+        (n, carry)
+    }
+
+    /// Returns the difference and whether it underflowed.
+    pub(crate) fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        let mut n: U256 = U256::ZERO;
+        let mut borrow: bool = false;
+        for i in 0..4 {
+            let (d, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            n.0[i] = d;
+            borrow = b1 || b2;
+        }
+        (n, borrow)
+    }
+
+    /// Returns the product and whether it overflowed.
+    pub(crate) fn overflowing_mul_u64(self, m: u64) -> (Self, bool) {
+        let mut n: U256 = U256::ZERO;
+        let mut carry: u128 = 0;
+        for i in 0..4 {
+            let p: u128 = (self.0[i] as u128) * (m as u128) + carry;
+            n.0[i] = p as u64;
+            carry = p >> 64;
+        }
+        (n, carry != 0)
+    }
+
+    /// Returns the quotient, rounding down. The divisor must not be zero.
+    pub(crate) fn div_u64(self, d: u64) -> Self {
+        let mut n: U256 = U256::ZERO;
+        let mut rem: u128 = 0;
+        for i in (0..4).rev() {
+            let cur: u128 = (rem << 64) | self.0[i] as u128;
+            n.0[i] = (cur / d as u128) as u64;
+            rem = cur % d as u128;
+        }
+        n
+    }
+
+    /// Returns the quotient, rounding down, by binary long division. The divisor must not be zero.
+    pub(crate) fn div(self, d: Self) -> Self {
+        let mut quotient: U256 = U256::ZERO;
+        let mut rem: U256 = U256::ZERO;
+        for i in (0..self.bits()).rev() {
+            rem = rem.shl(1);
+            rem.0[0] |= (self.0[(i / 64) as usize] >> (i % 64)) & 1;
+            if rem >= d {
+                rem = rem.overflowing_sub(d).0;
+                quotient.0[(i / 64) as usize] |= 1 << (i % 64);
+            }
+        }
+        quotient
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const S: usize> Digest<S> {
+    /// Compares two digests as unsigned little-endian integers, i.e. starting from the last byte.
+    /// This is how Bitcoin compares block hashes to targets.
+    pub fn cmp_le(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+
+    /// Compares two digests as unsigned big-endian integers, i.e. starting from the first byte.
+    pub fn cmp_be(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+
+    /// Returns the number of leading zero bits of the digest as an unsigned little-endian integer.
+    /// For a Bitcoin block hash this is the number of zero bits at the start of its usual
+    /// (reversed) hexidecimal form.
+    pub fn leading_zero_bits(&self) -> u32 {
+        match self.0.iter().rev().position(|b| *b != 0) {
+            Some(i) => 8 * i as u32 + self.0[S - 1 - i].leading_zeros(),
+            None => 8 * S as u32,
+        }
+    }
+}
+
+impl Digest<32> {
+    /// Decodes a target from the compact "nBits" format of a block header, and returns it as a
+    /// little-endian integer that can be compared to block hashes with ```cmp_le()```. The high
+    /// byte is the length of the target in bytes and the low three bytes are its most significant
+    /// bytes. Targets that are negative or do not fit in 256 bits are rejected.
+    pub fn from_compact(bits: u32) -> Result<Digest<32>> {
+        let size: u32 = bits >> 24;
+        let word: u32 = bits & 0x007fffff;
+        if word != 0 && bits & 0x00800000 != 0 {
+            return Err(Error::new(ErrorKind::InvalidTarget, "The compact target is negative."));
+        }
+        if word != 0 && (size > 34 || (word > 0xff && size > 33) || (word > 0xffff && size > 32)) {
+            return Err(Error::new(ErrorKind::InvalidTarget, "The compact target does not fit in 256 bits."));
+        }
+        let target: U256 = if size <= 3 {
+            U256::from_u64((word >> (8 * (3 - size))) as u64)
+        } else {
+            U256::from_u64(word as u64).shl(8 * (size - 3))
+        };
+        Ok(Digest(target.to_le_bytes()))
+    }
+
+    /// Encodes the digest, as a little-endian integer, in the compact "nBits" format. Only the
+    /// three most significant bytes are kept, so the result may round the target down.
+    pub fn to_compact(&self) -> u32 {
+        let target: U256 = U256::from_le_bytes(&self.0);
+        let mut size: u32 = target.bits().div_ceil(8);
+        let mut compact: u32 = if size <= 3 {
+            (target.low_u64() << (8 * (3 - size))) as u32
+        } else {
+            target.shr(8 * (size - 3)).low_u64() as u32
+        };
+        // the high bit of the mantissa is a sign bit, so shift it out of the way
+        if compact & 0x00800000 != 0 {
+            compact >>= 8;
+            size += 1;
+        }
+        compact | (size << 24)
+    }
+
+    /// Returns true if the digest, as a little-endian integer, is not greater than ```target```.
+    /// This is the proof of work check for a block hash.
+    pub fn meets_target(&self, target: &Digest<32>) -> bool {
+        self.cmp_le(target) != Ordering::Greater
+    }
+
+    /// Returns the expected number of hashes needed to find a hash that meets the digest as a
+    /// target, i.e. ```2^256 / (target + 1)```, as a little-endian integer. This is the work that
+    /// a block adds to the chain. Fails if the target is zero, since no hash can meet it.
+    pub fn chainwork(&self) -> Result<Digest<32>> {
+        let target: U256 = U256::from_le_bytes(&self.0);
+        if target.is_zero() {
+            return Err(Error::new(ErrorKind::InvalidTarget, "No hash can meet a target of zero."));
+        }
+        // 2^256 does not fit, but 2^256 / (target + 1) = (2^256 - target - 1) / (target + 1) + 1
+        let (plus_one, overflow) = target.overflowing_add(U256::ONE);
+        let work: U256 = if overflow {
+            U256::ONE
+        } else {
+            target.not().div(plus_one).overflowing_add(U256::ONE).0
+        };
+        Ok(Digest(work.to_le_bytes()))
+    }
+}
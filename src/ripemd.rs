@@ -0,0 +1,246 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::sha2::Sha256;
+use crate::OneWayHasher;
+
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160.html
+// https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+// https://en.bitcoin.it/wiki/Technical_background_of_version_1_Bitcoin_addresses
+
+/// An array used to initialize the five 32-bit chaining variables.
+const INITIAL_VALUES_160: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// The additive constants for each of the five rounds of the left line.
+const CONSTANTS_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+
+/// The additive constants for each of the five rounds of the right line.
+const CONSTANTS_RIGHT: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// The order in which the message words are selected by the left line.
+const WORDS_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+/// The order in which the message words are selected by the right line.
+const WORDS_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The amounts by which the left line rotates each step.
+const ROTATIONS_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+/// The amounts by which the right line rotates each step.
+const ROTATIONS_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// The five boolean functions used by the rounds. The left line uses them in the order
+/// f1..f5 and the right line uses them in the reverse order.
+#[inline(always)]
+fn boolean_fn(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+pub struct Ripemd160 {
+    st: [u32; 5],
+    buf: [u8; 64],
+    buf_num: usize,
+    len: usize,
+}
+
+impl Ripemd160 {
+    /// Compression function. Processes the 64 bytes currently held in the buffer.
+    fn compress(&mut self) {
+        let mut x: [u32; 16] = [0; 16];
+        for (w, chunk) in x.iter_mut().zip(self.buf.chunks_exact(4)) {
+            *w = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+
+        let (mut al, mut bl, mut cl, mut dl, mut el) = (self.st[0], self.st[1], self.st[2], self.st[3], self.st[4]);
+        let (mut ar, mut br, mut cr, mut dr, mut er) = (self.st[0], self.st[1], self.st[2], self.st[3], self.st[4]);
+
+        for j in 0..80 {
+            let round: usize = j / 16;
+
+            // left line
+            let t: u32 = al
+                .wrapping_add(boolean_fn(round, bl, cl, dl))
+                .wrapping_add(x[WORDS_LEFT[j]])
+                .wrapping_add(CONSTANTS_LEFT[round])
+                .rotate_left(ROTATIONS_LEFT[j])
+                .wrapping_add(el);
+            al = el;
+            el = dl;
+            dl = cl.rotate_left(10);
+            cl = bl;
+            bl = t;
+
+            // right line
+            let t: u32 = ar
+                .wrapping_add(boolean_fn(4 - round, br, cr, dr))
+                .wrapping_add(x[WORDS_RIGHT[j]])
+                .wrapping_add(CONSTANTS_RIGHT[round])
+                .rotate_left(ROTATIONS_RIGHT[j])
+                .wrapping_add(er);
+            ar = er;
+            er = dr;
+            dr = cr.rotate_left(10);
+            cr = br;
+            br = t;
+        }
+
+        // combine the two lines with the chaining variables
+        let t: u32 = self.st[1].wrapping_add(cl).wrapping_add(dr);
+        self.st[1] = self.st[2].wrapping_add(dl).wrapping_add(er);
+        self.st[2] = self.st[3].wrapping_add(el).wrapping_add(ar);
+        self.st[3] = self.st[4].wrapping_add(al).wrapping_add(br);
+        self.st[4] = self.st[0].wrapping_add(bl).wrapping_add(cr);
+        self.st[0] = t;
+    }
+}
+
+impl OneWayHasher<20> for Ripemd160 {
+    const NAME: &'static str = "ripemd160";
+    const BLOCK_SIZE: usize = 64;
+
+    #[inline]
+    fn init() -> Self {
+        Self {
+            st: INITIAL_VALUES_160,
+            buf: [0; 64],
+            buf_num: 0,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.st = INITIAL_VALUES_160;
+        self.buf = [0; 64];
+        self.buf_num = 0;
+        self.len = 0;
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        let mut bytes_copied: usize = 0;
+        while bytes_copied < data.len() {
+            let len: usize = (data.len() - bytes_copied).min(64 - self.buf_num);
+            self.buf[self.buf_num..(self.buf_num + len)]
+                .clone_from_slice(&data[bytes_copied..(bytes_copied + len)]);
+            bytes_copied += len;
+            self.buf_num += len;
+            self.len += len;
+            if self.buf_num == 64 {
+                self.compress();
+                self.buf_num = 0;
+            }
+        }
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 20]) {
+        // the length is appended in little-endian order, unlike sha-2
+        let bit_len: u64 = (self.len as u64).wrapping_mul(8);
+        let mut pad: [u8; 72] = [0; 72];
+        pad[0] = 128u8;
+        let pad_len: usize = if self.buf_num < 56 { 56 - self.buf_num } else { 120 - self.buf_num };
+        pad[pad_len..(pad_len + 8)].clone_from_slice(&bit_len.to_le_bytes());
+        self.update(&pad[..(pad_len + 8)]);
+        for (i, w) in digest.chunks_exact_mut(4).enumerate() {
+            w.clone_from_slice(&self.st[i].to_le_bytes());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Ripemd160 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Bitcoin's HASH160, which is RIPEMD160(SHA256(x)). It is used to derive the public key
+/// hashes found in P2PKH and P2WPKH scripts as well as P2SH script hashes.
+pub struct Hash160 {
+    inner: Sha256,
+    outer: Ripemd160,
+}
+
+impl OneWayHasher<20> for Hash160 {
+    const NAME: &'static str = "hash160";
+    const BLOCK_SIZE: usize = 64;
+
+    #[inline]
+    fn init() -> Self {
+        Self {
+            inner: Sha256::init(),
+            outer: Ripemd160::init(),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.inner.reset();
+        self.outer.reset();
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.inner.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 20]) {
+        let mut sha: [u8; 32] = [0; 32];
+        self.inner.finish_into(&mut sha);
+        self.outer.update(&sha).finish_into(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Hash160 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -4,15 +4,15 @@
 
 use crate::OneWayHasher;
 use crate::error::{Error, ErrorKind, Result};
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
-/// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
-/// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
-/// https://en.wikipedia.org/wiki/SHA-2#Comparison_of_SHA_functions
-/// https://en.wikipedia.org/wiki/Length_extension_attack
+// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+// https://en.wikipedia.org/wiki/SHA-2#Comparison_of_SHA_functions
+// https://en.wikipedia.org/wiki/Length_extension_attack
 
 /// An array of 64 constants consisting of the first 32 bits of the fractional parts of the cube roots of the first 64 primes 2 through 311.
-const CONSTANTS_256: [u32; 64] = [
+pub(crate) const CONSTANTS_256: [u32; 64] = [
     0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
     0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
     0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
@@ -111,7 +111,7 @@ const INITIAL_VALUES_224: [u32; 8] = [
     0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
 ];
 
-const INITIAL_VALUES_256: [u32; 8] = [
+pub(crate) const INITIAL_VALUES_256: [u32; 8] = [
     0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
 ];
 
@@ -191,6 +191,139 @@ macro_rules! new_context {
     };
 }
 
+/// The state of a SHA-2 context after a whole number of chunks have been absorbed. A midstate
+/// can be saved with ```to_bytes()``` and later passed to ```Context::from_midstate()``` to carry
+/// on hashing where it left off, e.g. to hash the first 64 bytes of a block header only once when
+/// grinding nonces. A midstate must only be resumed by the same SHA-2 variant that exported it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Midstate<T: Copy> {
+    /// The eight working hash values.
+    pub state: [T; 8],
+    /// The number of message bytes absorbed so far.
+    pub len: u64,
+}
+
+/// The midstate of Sha224 and Sha256.
+pub type Midstate256 = Midstate<u32>;
+
+/// The midstate of Sha384, Sha512, Sha512_224 and Sha512_256.
+pub type Midstate512 = Midstate<u64>;
+
+/// Implements serialization for a midstate. The hash values are written big-endian, followed by
+/// the big-endian length. Parameters are as follows:
+///    $typ - The word type of the midstate (u32 or u64)
+///    $size - The size of the serialized midstate in bytes
+macro_rules! midstate_bytes {
+    ($typ:ty, $size:literal) => {
+        impl Midstate<$typ> {
+            /// The size of the serialized midstate in bytes.
+            pub const SIZE: usize = $size;
+
+            /// Serializes the midstate into an array of bytes.
+            pub fn to_bytes(&self) -> [u8; $size] {
+                const WORD: usize = core::mem::size_of::<$typ>();
+                let mut bytes: [u8; $size] = [0; $size];
+                for (b, w) in bytes.chunks_exact_mut(WORD).zip(self.state.iter()) {
+                    b.copy_from_slice(&w.to_be_bytes());
+                }
+                bytes[($size - 8)..].copy_from_slice(&self.len.to_be_bytes());
+                bytes
+            }
+
+            /// Deserializes a midstate from a slice of bytes created by ```to_bytes()```.
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+                const WORD: usize = core::mem::size_of::<$typ>();
+                if bytes.len() != $size {
+                    return Err(Error::new(ErrorKind::InvalidSliceLength, "Slice length is not equal to the size of a midstate."));
+                }
+                let mut state: [$typ; 8] = [0; 8];
+                for (w, b) in state.iter_mut().zip(bytes.chunks_exact(WORD)) {
+                    *w = <$typ>::from_be_bytes(b.try_into().unwrap());
+                }
+                let len: u64 = u64::from_be_bytes(bytes[($size - 8)..].try_into().unwrap());
+                Ok(Self { state, len })
+            }
+        }
+    };
+}
+
+midstate_bytes!(u32, 40);
+midstate_bytes!(u64, 72);
+
+impl<const B: usize, const W: usize, const S: usize, T: Copy + 'static + Default> Context<B, W, S, T> {
+    /// The number of bytes in a chunk, i.e. 64 for the 32 bit variants and 128 for the 64 bit ones.
+    const CHUNK_LEN: usize = 16 * core::mem::size_of::<T>();
+
+    /// Returns the midstate of the context. This fails unless the number of bytes absorbed so far
+    /// is a multiple of the chunk size, since a partially filled chunk is not part of the state.
+    pub fn midstate(&self) -> Result<Midstate<T>> {
+        if self.msg_num != 0 {
+            Err(Error::new(ErrorKind::InvalidDataLength, "A midstate requires a whole number of chunks to have been absorbed."))
+        } else {
+            Ok(Midstate { state: self.st, len: self.len as u64 })
+        }
+    }
+
+    /// Replaces the state of the context with ```midstate```, discarding any data absorbed so far.
+    /// Fails if the length does not fit in a ```usize```, which is possible on 32 bit targets.
+    pub fn set_midstate(&mut self, midstate: &Midstate<T>) -> Result<&mut Self> {
+        if midstate.len % Self::CHUNK_LEN as u64 != 0 {
+            return Err(Error::new(ErrorKind::InvalidDataLength, "Midstate length is not a multiple of the chunk size."));
+        }
+        let len: usize = usize::try_from(midstate.len)
+            .map_err(|_| Error::new(ErrorKind::InvalidParameter, "Midstate length does not fit in a usize."))?;
+        self.st = midstate.state;
+        self.msg_num = 0;
+        self.len = len;
+        Ok(self)
+    }
+
+    /// Sets the hash values to ```initial_values``` and clears the message schedule. With the
+    /// ```zeroize``` feature the old state and schedule are wiped first.
+    #[inline]
+    fn clear(&mut self, initial_values: [T; 8]) {
+        #[cfg(feature = "zeroize")]
+        unsafe {
+            crate::wipe(&mut self.st);
+            crate::wipe(&mut self.msg_sch);
+        }
+        #[cfg(not(feature = "zeroize"))]
+        {
+            self.msg_sch = MsgSch::new();
+        }
+        self.st = initial_values;
+        self.msg_num = 0;
+        self.len = 0;
+    }
+
+    /// Returns a new context that resumes hashing from ```midstate```.
+    pub fn from_midstate(midstate: &Midstate<T>) -> Result<Self> {
+        let mut ctx: Self = Self {
+            st: midstate.state,
+            msg_sch: MsgSch::new(),
+            msg_num: 0,
+            len: 0,
+            _t: PhantomData,
+        };
+        ctx.set_midstate(midstate)?;
+        Ok(ctx)
+    }
+}
+
+/// Wipes the state and the message schedule, which holds the last chunk of the message.
+#[cfg(feature = "zeroize")]
+impl<const B: usize, const W: usize, const S: usize, T: Copy + 'static + Default> Drop for Context<B, W, S, T> {
+    fn drop(&mut self) {
+        unsafe {
+            crate::wipe(&mut self.st);
+            crate::wipe(&mut self.msg_sch);
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const B: usize, const W: usize, const S: usize, T: Copy + 'static + Default> zeroize::ZeroizeOnDrop for Context<B, W, S, T> {}
+
 /// Extend the first 16 words into the remaining words of the message schedule
 /// Parameters are as follows:
 ///    $s - A mutable reference to a Context struct
@@ -286,15 +419,23 @@ macro_rules! transform {
 
 macro_rules! wrap_up {
     ($s:ident, $typ:ty, $digest:ident, $digest_len:literal, $chunk_len:literal) => {
-        let mut buf: Vec<u8> = Vec::new();
-        buf.push(128u8);
-        while (buf.len() + $s.msg_num + std::mem::size_of::<$typ>()) % $chunk_len != 0 {
-            buf.push(0u8);
+        // pad the message in place: a single 1 bit, zeros, then the message length in bits
+        const LEN_SIZE: usize = core::mem::size_of::<$typ>();
+        let bit_len: $typ = ($s.len as $typ) * 8;
+        unsafe {
+            $s.msg_sch.b[$s.msg_num] = 128u8;
+            $s.msg_sch.b[($s.msg_num + 1)..$chunk_len].fill(0);
+            if $s.msg_num + 1 > $chunk_len - LEN_SIZE {
+                // the length does not fit in this chunk, so it goes in one more chunk of zeros
+                $s.compress_chunk();
+                $s.msg_sch.b[..$chunk_len].fill(0);
+            }
+            $s.msg_sch.b[($chunk_len - LEN_SIZE)..$chunk_len].copy_from_slice(&bit_len.to_be_bytes());
+            $s.compress_chunk();
         }
-        buf.extend_from_slice(&(($s.len * 8) as $typ).to_be_bytes());
-        $s.update(&buf);
+        $s.msg_num = 0;
         for (i, w) in $digest
-            .chunks_exact_mut(std::mem::size_of::<$typ>() / 2)
+            .chunks_exact_mut(core::mem::size_of::<$typ>() / 2)
             .enumerate()
         {
             w.clone_from_slice(&$s.st[i].to_be_bytes());
@@ -302,9 +443,86 @@ macro_rules! wrap_up {
     };
 }
 
+impl<const S: usize> Context<256, 64, S, u32> {
+    /// Compresses the chunk held in the message schedule, using the SHA extensions when the CPU
+    /// supports them.
+    #[inline]
+    unsafe fn compress_chunk(&mut self) {
+        #[cfg(target_arch = "x86_64")]
+        if crate::sha_ni::is_available() {
+            crate::sha_ni::compress(&mut self.st, &self.msg_sch.b[..64]);
+            return;
+        }
+        extend_msg_schedule!(self, u32, 64, 7, 18, 3, 17, 19, 10);
+        compression_loop!(self, u32, CONSTANTS_256, 2, 13, 22, 6, 11, 25);
+    }
+
+    /// Shared by Sha224 and Sha256. When the SHA extensions are available, whole chunks are
+    /// compressed straight from ```data``` instead of being copied into the message schedule first.
+    #[inline]
+    fn update_256(&mut self, data: &[u8]) {
+        #[cfg(target_arch = "x86_64")]
+        let data: &[u8] = if crate::sha_ni::is_available() {
+            // top up a partially filled chunk first
+            let len: usize = if self.msg_num > 0 { data.len().min(64 - self.msg_num) } else { 0 };
+            let (head, tail) = data.split_at(len);
+            unsafe {
+                transform!(self, head, 64, {}, { self.compress_chunk(); });
+            }
+            let len: usize = tail.len() - tail.len() % 64;
+            unsafe { crate::sha_ni::compress(&mut self.st, &tail[..len]) };
+            self.len += len;
+            &tail[len..]
+        } else {
+            data
+        };
+        unsafe {
+            transform!(self, data, 64, {}, { self.compress_chunk(); });
+        }
+    }
+}
+
+impl<const S: usize> Context<320, 80, S, u64> {
+    /// Compresses the chunk held in the message schedule.
+    #[inline]
+    unsafe fn compress_chunk(&mut self) {
+        extend_msg_schedule!(self, u64, 80, 1, 8, 7, 19, 61, 6);
+        compression_loop!(self, u64, CONSTANTS_512, 28, 34, 39, 14, 18, 41);
+    }
+}
+
+/// Runs the SHA-256 compression function over each chunk in ```chunks```, updating ```state```.
+/// This uses the SHA extensions when the CPU supports them and the portable implementation
+/// otherwise. No padding is applied.
+pub fn compress256(state: &mut [u32; 8], chunks: &[[u8; 64]]) {
+    #[cfg(target_arch = "x86_64")]
+    if crate::sha_ni::is_available() {
+        unsafe { crate::sha_ni::compress(state, chunks.as_flattened()) };
+        return;
+    }
+    compress256_portable(state, chunks)
+}
+
+/// The same as ```compress256()```, except that it always uses the portable implementation.
+pub fn compress256_portable(state: &mut [u32; 8], chunks: &[[u8; 64]]) {
+    let mut ctx: Sha256 = Sha256::init();
+    ctx.st = *state;
+    for chunk in chunks {
+        unsafe {
+            ctx.msg_sch.b[..64].clone_from_slice(chunk);
+            extend_msg_schedule!(ctx, u32, 64, 7, 18, 3, 17, 19, 10);
+            compression_loop!(ctx, u32, CONSTANTS_256, 2, 13, 22, 6, 11, 25);
+        }
+    }
+    *state = ctx.st;
+}
+
 pub type Sha224 = Context<256, 64, 28, u32>;
 
 impl OneWayHasher<28> for Sha224 {
+    const NAME: &'static str = "sha224";
+    const BLOCK_SIZE: usize = 64;
+
     #[inline]
     fn init() -> Self {
         new_context!(INITIAL_VALUES_224)
@@ -312,43 +530,23 @@ impl OneWayHasher<28> for Sha224 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = INITIAL_VALUES_224;
-        self.msg_sch = MsgSch { b: [0; 256] };
-        self.msg_num = 0;
-        self.len = 0;
+        self.clear(INITIAL_VALUES_224);
         self
     }
 
     #[inline]
     fn update(&mut self, data: &[u8]) -> &mut Self {
-        unsafe {
-            transform!(
-                self,
-                data,
-                64,
-                {
-                    extend_msg_schedule!(self, u32, 64, 7, 18, 3, 17, 19, 10);
-                },
-                {
-                    compression_loop!(self, u32, CONSTANTS_256, 2, 13, 22, 6, 11, 25);
-                }
-            );
-            self
-        }
+        self.update_256(data);
+        self
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()> {
-    //fn finish(&mut self, digest: &mut [u8; 28]) {
-        if digest.len() != 28 {
-            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice length not equal to digest length of 28."))
-        } else {
-            wrap_up!(self, u64, digest, 28, 64);
-            Ok(())
-        }
+    fn finish_into(&mut self, digest: &mut [u8; 28]) {
+        wrap_up!(self, u64, digest, 28, 64);
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha224 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         self.update(bytes);
@@ -363,6 +561,9 @@ impl std::io::Write for Sha224 {
 pub type Sha256 = Context<256, 64, 32, u32>;
 
 impl OneWayHasher<32> for Sha256 {
+    const NAME: &'static str = "sha256";
+    const BLOCK_SIZE: usize = 64;
+
     #[inline]
     fn init() -> Self {
         new_context!(INITIAL_VALUES_256)
@@ -370,43 +571,28 @@ impl OneWayHasher<32> for Sha256 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = INITIAL_VALUES_256;
-        self.msg_sch = MsgSch { b: [0; 256] };
-        self.msg_num = 0;
-        self.len = 0;
+        self.clear(INITIAL_VALUES_256);
         self
     }
 
     #[inline]
     fn update(&mut self, data: &[u8]) -> &mut Self {
-        unsafe {
-            transform!(
-                self,
-                data,
-                64,
-                {
-                    extend_msg_schedule!(self, u32, 64, 7, 18, 3, 17, 19, 10);
-                },
-                {
-                    compression_loop!(self, u32, CONSTANTS_256, 2, 13, 22, 6, 11, 25);
-                }
-            );
-            self
-        }
+        self.update_256(data);
+        self
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()> {
-    //fn finish(&mut self, digest: &mut [u8; 32]) {
-        if digest.len() != 32 {
-            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice lenght is not equal to digest length of 32."))
-        } else {
-            wrap_up!(self, u64, digest, 32, 64);
-            Ok(())
-        }
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        wrap_up!(self, u64, digest, 32, 64);
+    }
+
+    #[inline]
+    fn hash_pairs(&mut self, nodes: &mut [[u8; 32]]) -> Result<()> {
+        crate::multibuffer::hash_pairs(nodes, crate::multibuffer::sha256_many)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha256 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         self.update(bytes);
@@ -421,6 +607,9 @@ impl std::io::Write for Sha256 {
 pub type Sha384 = Context<320, 80, 48, u64>;
 
 impl OneWayHasher<48> for Sha384 {
+    const NAME: &'static str = "sha384";
+    const BLOCK_SIZE: usize = 128;
+
     #[inline]
     fn init() -> Self {
         new_context!(INITIAL_VALUES_384)
@@ -428,43 +617,25 @@ impl OneWayHasher<48> for Sha384 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = INITIAL_VALUES_384;
-        self.msg_sch = MsgSch { b: [0; 320] };
-        self.msg_num = 0;
-        self.len = 0;
+        self.clear(INITIAL_VALUES_384);
         self
     }
 
     #[inline]
     fn update(&mut self, data: &[u8]) -> &mut Self {
         unsafe {
-            transform!(
-                self,
-                data,
-                128,
-                {
-                    extend_msg_schedule!(self, u64, 80, 1, 8, 7, 19, 61, 6);
-                },
-                {
-                    compression_loop!(self, u64, CONSTANTS_512, 28, 34, 39, 14, 18, 41);
-                }
-            );
+            transform!(self, data, 128, {}, { self.compress_chunk(); });
             self
         }
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()> {
-    //fn finish(&mut self, digest: &mut [u8; 48]) {
-        if digest.len() != 48 {
-            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice lenght is not equal to digest length of 48."))
-        } else {
-            wrap_up!(self, u128, digest, 48, 128);
-            Ok(())
-        }
+    fn finish_into(&mut self, digest: &mut [u8; 48]) {
+        wrap_up!(self, u128, digest, 48, 128);
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha384 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         self.update(bytes);
@@ -479,6 +650,9 @@ impl std::io::Write for Sha384 {
 pub type Sha512 = Context<320, 80, 64, u64>;
 
 impl OneWayHasher<64> for Sha512 {
+    const NAME: &'static str = "sha512";
+    const BLOCK_SIZE: usize = 128;
+
     #[inline]
     fn init() -> Self {
         new_context!(INITIAL_VALUES_512)
@@ -486,43 +660,25 @@ impl OneWayHasher<64> for Sha512 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = INITIAL_VALUES_512;
-        self.msg_sch = MsgSch { b: [0; 320] };
-        self.msg_num = 0;
-        self.len = 0;
+        self.clear(INITIAL_VALUES_512);
         self
     }
 
     #[inline]
     fn update(&mut self, data: &[u8]) -> &mut Self {
         unsafe {
-            transform!(
-                self,
-                data,
-                128,
-                {
-                    extend_msg_schedule!(self, u64, 80, 1, 8, 7, 19, 61, 6);
-                },
-                {
-                    compression_loop!(self, u64, CONSTANTS_512, 28, 34, 39, 14, 18, 41);
-                }
-            );
+            transform!(self, data, 128, {}, { self.compress_chunk(); });
             self
         }
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()> {
-    //fn finish(&mut self, digest: &mut [u8; 64]) {
-        if digest.len() != 64 {
-            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice length not equal to digest length of 64."))
-        } else {
-            wrap_up!(self, u128, digest, 64, 128);
-            Ok(())
-        }
+    fn finish_into(&mut self, digest: &mut [u8; 64]) {
+        wrap_up!(self, u128, digest, 64, 128);
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha512 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         self.update(bytes);
@@ -537,6 +693,9 @@ impl std::io::Write for Sha512 {
 pub type Sha512_224 = Context<320, 80, 28, u64>;
 
 impl OneWayHasher<28> for Sha512_224 {
+    const NAME: &'static str = "sha512/224";
+    const BLOCK_SIZE: usize = 128;
+
     #[inline]
     fn init() -> Self {
         new_context!(INITIAL_VALUES_512_224)
@@ -544,45 +703,27 @@ impl OneWayHasher<28> for Sha512_224 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = INITIAL_VALUES_512_224;
-        self.msg_sch = MsgSch { b: [0; 320] };
-        self.msg_num = 0;
-        self.len = 0;
+        self.clear(INITIAL_VALUES_512_224);
         self
     }
 
     #[inline]
     fn update(&mut self, data: &[u8]) -> &mut Self {
         unsafe {
-            transform!(
-                self,
-                data,
-                128,
-                {
-                    extend_msg_schedule!(self, u64, 80, 1, 8, 7, 19, 61, 6);
-                },
-                {
-                    compression_loop!(self, u64, CONSTANTS_512, 28, 34, 39, 14, 18, 41);
-                }
-            );
+            transform!(self, data, 128, {}, { self.compress_chunk(); });
             self
         }
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()> {
-    //fn finish(&mut self, digest: &mut [u8; 28]) {
-        if digest.len() != 28 {
-            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice length is not equal to digest lenght of 28."))
-        } else {
-            wrap_up!(self, u128, digest, 28, 128);
-            // fill in the last four bytes
-            digest[24..28].clone_from_slice(&self.st[3].to_be_bytes()[0..4]);
-            Ok(())
-        }
+    fn finish_into(&mut self, digest: &mut [u8; 28]) {
+        wrap_up!(self, u128, digest, 28, 128);
+        // fill in the last four bytes
+        digest[24..28].clone_from_slice(&self.st[3].to_be_bytes()[0..4]);
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha512_224 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         self.update(bytes);
@@ -597,6 +738,9 @@ impl std::io::Write for Sha512_224 {
 pub type Sha512_256 = Context<320, 80, 32, u64>;
 
 impl OneWayHasher<32> for Sha512_256 {
+    const NAME: &'static str = "sha512/256";
+    const BLOCK_SIZE: usize = 128;
+
     #[inline]
     fn init() -> Self {
         new_context!(INITIAL_VALUES_512_256)
@@ -604,43 +748,25 @@ impl OneWayHasher<32> for Sha512_256 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = INITIAL_VALUES_512_256;
-        self.msg_sch = MsgSch { b: [0; 320] };
-        self.msg_num = 0;
-        self.len = 0;
+        self.clear(INITIAL_VALUES_512_256);
         self
     }
 
     #[inline]
     fn update(&mut self, data: &[u8]) -> &mut Self {
         unsafe {
-            transform!(
-                self,
-                data,
-                128,
-                {
-                    extend_msg_schedule!(self, u64, 80, 1, 8, 7, 19, 61, 6);
-                },
-                {
-                    compression_loop!(self, u64, CONSTANTS_512, 28, 34, 39, 14, 18, 41);
-                }
-            );
+            transform!(self, data, 128, {}, { self.compress_chunk(); });
             self
         }
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8]) -> Result<()> {
-    //fn finish(&mut self, digest: &mut [u8; 32]) {
-        if digest.len() != 32 {
-            Err(Error::new(ErrorKind::InvalidSliceLength, "Slice length not equal to digest lenght of 32."))
-        } else {
-            wrap_up!(self, u128, digest, 32, 128);
-            Ok(())
-        }
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        wrap_up!(self, u128, digest, 32, 128);
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha512_256 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         self.update(bytes);
@@ -651,3 +777,57 @@ impl std::io::Write for Sha512_256 {
         Ok(())
     }
 }
+
+/// SHA-256 applied twice, i.e. SHA256(SHA256(x)). This is the hash function Bitcoin uses for
+/// block headers, transaction ids, and the nodes of its merkle trees. Note that Bitcoin displays
+/// these digests with their bytes reversed. See ```Digest::to_rev_string()``` and
+/// ```Digest::from_rev_str()```.
+pub struct Sha256d {
+    ctx: Sha256,
+}
+
+impl OneWayHasher<32> for Sha256d {
+    const NAME: &'static str = "sha256d";
+    const BLOCK_SIZE: usize = 64;
+
+    #[inline]
+    fn init() -> Self {
+        Self { ctx: Sha256::init() }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx.reset();
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        let mut first: [u8; 32] = [0; 32];
+        self.ctx.finish_into(&mut first);
+        self.ctx.reset().update(&first).finish_into(digest)
+    }
+
+    #[inline]
+    fn hash_pairs(&mut self, nodes: &mut [[u8; 32]]) -> Result<()> {
+        crate::multibuffer::hash_pairs(nodes, crate::multibuffer::sha256d_many)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Sha256d {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
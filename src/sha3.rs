@@ -4,7 +4,7 @@
 
 use crate::FinishXOF;
 use crate::OneWayHasher;
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 const KECCAKF_RNDC: [u64; 24] = [
     0x0000000000000001,
@@ -55,7 +55,7 @@ pub struct Context<const B: usize, const D: usize> {
 }
 
 impl<const B: usize, const D: usize> Context<B, D> {
-    fn init() -> Context<B, D> {
+    pub(crate) fn init() -> Context<B, D> {
         Self {
             st: State { q: [0; 25] },
             pt: 0,
@@ -64,24 +64,59 @@ impl<const B: usize, const D: usize> Context<B, D> {
         }
     }
 
+    /// zero the sponge state and start absorbing from the beginning of a block. with the zeroize
+    /// feature the old state is wiped in a way that the compiler cannot optimize away
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        #[cfg(feature = "zeroize")]
+        unsafe {
+            crate::wipe(&mut self.st);
+        }
+        #[cfg(not(feature = "zeroize"))]
+        {
+            self.st = State { q: [0; 25] };
+        }
+        self.pt = 0;
+    }
+
     /// update state with more data
-    fn update(&mut self, data: &[u8]) {
+    pub(crate) fn update(&mut self, data: &[u8]) {
         unsafe {
-            let mut j: usize = self.pt;
-            for byte in data {
-                self.st.b[j] ^= byte;
-                j += 1;
-                if j >= self.rsiz {
-                    self.keccakf();
-                    j = 0;
+            // top up a partially absorbed block
+            let len: usize = if self.pt > 0 { data.len().min(self.rsiz - self.pt) } else { 0 };
+            let (head, mut data) = data.split_at(len);
+            self.absorb_bytes(head);
+
+            // absorb whole blocks a 64-bit lane at a time
+            while data.len() >= self.rsiz {
+                let (block, rest) = data.split_at(self.rsiz);
+                for (q, lane) in self.st.q.iter_mut().zip(block.chunks_exact(8)) {
+                    // the state holds the lanes in little-endian byte order between permutations
+                    *q ^= u64::from_ne_bytes(lane.try_into().unwrap());
                 }
+                self.keccakf();
+                data = rest;
             }
-            self.pt = j;
+
+            self.absorb_bytes(data);
+        }
+    }
+
+    /// xor bytes into the state without crossing the end of a block, then permute if the block is full
+    #[inline]
+    unsafe fn absorb_bytes(&mut self, data: &[u8]) {
+        for (b, byte) in self.st.b[self.pt..(self.pt + data.len())].iter_mut().zip(data) {
+            *b ^= byte;
+        }
+        self.pt += data.len();
+        if self.pt == self.rsiz {
+            self.keccakf();
+            self.pt = 0;
         }
     }
 
     /// finalize and output a hash
-    fn finish(&mut self, digest: &mut [u8; D]) {
+    fn sha3_finish(&mut self, digest: &mut [u8; D]) {
         unsafe {
             self.st.b[self.pt] ^= 0x06;
             self.st.b[self.rsiz - 1] ^= 0x80;
@@ -90,7 +125,17 @@ impl<const B: usize, const D: usize> Context<B, D> {
         }
     }
 
-    fn shake_xof(&mut self) {
+    /// finalize using the original Keccak padding (0x01) that predates FIPS 202
+    fn keccak_finish(&mut self, digest: &mut [u8; D]) {
+        unsafe {
+            self.st.b[self.pt] ^= 0x01;
+            self.st.b[self.rsiz - 1] ^= 0x80;
+            self.keccakf();
+            digest.copy_from_slice(&self.st.b[..D]);
+        }
+    }
+
+    pub(crate) fn shake_xof(&mut self) {
         unsafe {
             self.st.b[self.pt] ^= 0x1F;
             self.st.b[self.rsiz - 1] ^= 0x80;
@@ -99,18 +144,42 @@ impl<const B: usize, const D: usize> Context<B, D> {
         }
     }
 
-    fn shake_out(&mut self, digest: &mut [u8]) {
+    /// pad using the cSHAKE domain separation bits (0x04) and prepare to squeeze
+    #[cfg(feature = "alloc")]
+    pub(crate) fn cshake_xof(&mut self) {
+        unsafe {
+            self.st.b[self.pt] ^= 0x04;
+            self.st.b[self.rsiz - 1] ^= 0x80;
+            self.keccakf();
+            self.pt = 0;
+        }
+    }
+
+    pub(crate) fn shake_out(&mut self, digest: &mut [u8]) {
         unsafe {
-            let mut j = self.pt;
-            for byte in digest {
-                if j >= self.rsiz {
+            let mut digest: &mut [u8] = digest;
+            while !digest.is_empty() {
+                if self.pt >= self.rsiz {
                     self.keccakf();
-                    j = 0;
+                    self.pt = 0;
                 }
-                *byte = self.st.b[j];
-                j += 1;
+                // copy as much of the current block as is available in one go
+                let len: usize = digest.len().min(self.rsiz - self.pt);
+                let (out, rest) = digest.split_at_mut(len);
+                out.copy_from_slice(&self.st.b[self.pt..(self.pt + len)]);
+                self.pt += len;
+                digest = rest;
             }
-            self.pt = j;
+        }
+    }
+
+    /// copy the sponge state into a new context, which may have a different digest length
+    fn fork<const E: usize>(&self) -> Context<B, E> {
+        Context {
+            st: State { q: unsafe { self.st.q } },
+            pt: self.pt,
+            rsiz: self.rsiz,
+            _s: PhantomData,
         }
     }
 
@@ -171,9 +240,25 @@ impl<const B: usize, const D: usize> Context<B, D> {
     }
 }
 
+/// wipe the sponge state, which holds the absorbed message until it is permuted
+#[cfg(feature = "zeroize")]
+impl<const B: usize, const D: usize> Drop for Context<B, D> {
+    fn drop(&mut self) {
+        unsafe {
+            crate::wipe(&mut self.st);
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const B: usize, const D: usize> zeroize::ZeroizeOnDrop for Context<B, D> {}
+
 pub type Sha3_224 = Context<28, 28>;
 
 impl OneWayHasher<28> for Sha3_224 {
+    const NAME: &'static str = "sha3-224";
+    const BLOCK_SIZE: usize = 144;
+
     #[inline]
     fn init() -> Sha3_224 {
         Context::<28, 28>::init()
@@ -181,8 +266,7 @@ impl OneWayHasher<28> for Sha3_224 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = State { q: [0; 25] };
-        self.pt = 0;
+        self.clear();
         self.rsiz = 200 - (2 * 28);
         self
     }
@@ -194,11 +278,12 @@ impl OneWayHasher<28> for Sha3_224 {
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8; 28]) {
-        self.finish(digest)
+    fn finish_into(&mut self, digest: &mut [u8; 28]) {
+        self.sha3_finish(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha3_224 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         Context::update(self, bytes);
@@ -213,6 +298,9 @@ impl std::io::Write for Sha3_224 {
 pub type Sha3_256 = Context<32, 32>;
 
 impl OneWayHasher<32> for Sha3_256 {
+    const NAME: &'static str = "sha3-256";
+    const BLOCK_SIZE: usize = 136;
+
     #[inline]
     fn init() -> Sha3_256 {
         Context::<32, 32>::init()
@@ -220,8 +308,7 @@ impl OneWayHasher<32> for Sha3_256 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = State { q: [0; 25] };
-        self.pt = 0;
+        self.clear();
         self.rsiz = 200 - (2 * 32);
         self
     }
@@ -233,11 +320,12 @@ impl OneWayHasher<32> for Sha3_256 {
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8; 32]) {
-        self.finish(digest)
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        self.sha3_finish(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha3_256 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         Context::update(self, bytes);
@@ -252,6 +340,9 @@ impl std::io::Write for Sha3_256 {
 pub type Sha3_384 = Context<48, 48>;
 
 impl OneWayHasher<48> for Sha3_384 {
+    const NAME: &'static str = "sha3-384";
+    const BLOCK_SIZE: usize = 104;
+
     #[inline]
     fn init() -> Sha3_384 {
         Context::<48, 48>::init()
@@ -259,8 +350,7 @@ impl OneWayHasher<48> for Sha3_384 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = State { q: [0; 25] };
-        self.pt = 0;
+        self.clear();
         self.rsiz = 200 - (2 * 48);
         self
     }
@@ -272,11 +362,12 @@ impl OneWayHasher<48> for Sha3_384 {
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8; 48]) {
-        self.finish(digest)
+    fn finish_into(&mut self, digest: &mut [u8; 48]) {
+        self.sha3_finish(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha3_384 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         Context::update(self, bytes);
@@ -291,6 +382,9 @@ impl std::io::Write for Sha3_384 {
 pub type Sha3_512 = Context<64, 64>;
 
 impl OneWayHasher<64> for Sha3_512 {
+    const NAME: &'static str = "sha3-512";
+    const BLOCK_SIZE: usize = 72;
+
     #[inline]
     fn init() -> Sha3_512 {
         Context::<64, 64>::init()
@@ -298,8 +392,7 @@ impl OneWayHasher<64> for Sha3_512 {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.st = State { q: [0; 25] };
-        self.pt = 0;
+        self.clear();
         self.rsiz = 200 - (2 * 64);
         self
     }
@@ -311,11 +404,12 @@ impl OneWayHasher<64> for Sha3_512 {
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8; 64]) {
-        self.finish(digest)
+    fn finish_into(&mut self, digest: &mut [u8; 64]) {
+        self.sha3_finish(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::io::Write for Sha3_512 {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         Context::update(self, bytes);
@@ -327,10 +421,208 @@ impl std::io::Write for Sha3_512 {
     }
 }
 
+/// Keccak-224 with the original pre-NIST padding.
+pub struct Keccak224 {
+    ctx: Context<28, 28>,
+}
+
+impl OneWayHasher<28> for Keccak224 {
+    const NAME: &'static str = "keccak-224";
+    const BLOCK_SIZE: usize = 144;
+
+    #[inline]
+    fn init() -> Keccak224 {
+        Keccak224 {
+            ctx: Context::<28, 28>::init(),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx.clear();
+        self.ctx.rsiz = 200 - (2 * 28);
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Keccak224 {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 28]) {
+        self.ctx.keccak_finish(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Keccak224 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        Context::update(&mut self.ctx, bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keccak-256 with the original pre-NIST padding, as used by Ethereum.
+pub struct Keccak256 {
+    ctx: Context<32, 32>,
+}
+
+impl OneWayHasher<32> for Keccak256 {
+    const NAME: &'static str = "keccak-256";
+    const BLOCK_SIZE: usize = 136;
+
+    #[inline]
+    fn init() -> Keccak256 {
+        Keccak256 {
+            ctx: Context::<32, 32>::init(),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx.clear();
+        self.ctx.rsiz = 200 - (2 * 32);
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Keccak256 {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        self.ctx.keccak_finish(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Keccak256 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        Context::update(&mut self.ctx, bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keccak-384 with the original pre-NIST padding.
+pub struct Keccak384 {
+    ctx: Context<48, 48>,
+}
+
+impl OneWayHasher<48> for Keccak384 {
+    const NAME: &'static str = "keccak-384";
+    const BLOCK_SIZE: usize = 104;
+
+    #[inline]
+    fn init() -> Keccak384 {
+        Keccak384 {
+            ctx: Context::<48, 48>::init(),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx.clear();
+        self.ctx.rsiz = 200 - (2 * 48);
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Keccak384 {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 48]) {
+        self.ctx.keccak_finish(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Keccak384 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        Context::update(&mut self.ctx, bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keccak-512 with the original pre-NIST padding.
+pub struct Keccak512 {
+    ctx: Context<64, 64>,
+}
+
+impl OneWayHasher<64> for Keccak512 {
+    const NAME: &'static str = "keccak-512";
+    const BLOCK_SIZE: usize = 72;
+
+    #[inline]
+    fn init() -> Keccak512 {
+        Keccak512 {
+            ctx: Context::<64, 64>::init(),
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx.clear();
+        self.ctx.rsiz = 200 - (2 * 64);
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Keccak512 {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 64]) {
+        self.ctx.keccak_finish(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Keccak512 {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        Context::update(&mut self.ctx, bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 pub struct Shake128<const MDLEN: usize> {
     ctx: Context<16, MDLEN>,
 }
 
+impl<const MDLEN: usize> Shake128<MDLEN> {
+    /// Finalizes a copy of the context and returns a reader that squeezes an unlimited amount
+    /// of output from it. The context itself is left untouched.
+    pub fn finish_reader(&self) -> XofReader<16> {
+        let mut ctx: Context<16, 0> = self.ctx.fork();
+        ctx.shake_xof();
+        XofReader { ctx }
+    }
+}
+
 impl<const MDLEN: usize> FinishXOF for Shake128<MDLEN> {
     fn finish_xof(&mut self, digest: &mut [u8]) {
         self.ctx.shake_xof();
@@ -339,6 +631,9 @@ impl<const MDLEN: usize> FinishXOF for Shake128<MDLEN> {
 }
 
 impl<const MDLEN: usize> OneWayHasher<MDLEN> for Shake128<MDLEN> {
+    const NAME: &'static str = "shake128";
+    const BLOCK_SIZE: usize = 168;
+
     #[inline]
     fn init() -> Shake128<MDLEN> {
         Shake128 {
@@ -348,8 +643,7 @@ impl<const MDLEN: usize> OneWayHasher<MDLEN> for Shake128<MDLEN> {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.ctx.st = State { q: [0; 25] };
-        self.ctx.pt = 0;
+        self.ctx.clear();
         self.ctx.rsiz = 200 - (2 * 16);
         self
     }
@@ -361,12 +655,13 @@ impl<const MDLEN: usize> OneWayHasher<MDLEN> for Shake128<MDLEN> {
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8; MDLEN]) {
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
         self.ctx.shake_xof();
         self.ctx.shake_out(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl<const MDLEN: usize> std::io::Write for Shake128<MDLEN> {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         Context::update(&mut self.ctx, bytes);
@@ -382,6 +677,16 @@ pub struct Shake256<const MDLEN: usize> {
     ctx: Context<32, MDLEN>,
 }
 
+impl<const MDLEN: usize> Shake256<MDLEN> {
+    /// Finalizes a copy of the context and returns a reader that squeezes an unlimited amount
+    /// of output from it. The context itself is left untouched.
+    pub fn finish_reader(&self) -> XofReader<32> {
+        let mut ctx: Context<32, 0> = self.ctx.fork();
+        ctx.shake_xof();
+        XofReader { ctx }
+    }
+}
+
 impl<const MDLEN: usize> FinishXOF for Shake256<MDLEN> {
     fn finish_xof(&mut self, digest: &mut [u8]) {
         self.ctx.shake_xof();
@@ -390,6 +695,9 @@ impl<const MDLEN: usize> FinishXOF for Shake256<MDLEN> {
 }
 
 impl<const MDLEN: usize> OneWayHasher<MDLEN> for Shake256<MDLEN> {
+    const NAME: &'static str = "shake256";
+    const BLOCK_SIZE: usize = 136;
+
     #[inline]
     fn init() -> Shake256<MDLEN> {
         Shake256 {
@@ -399,8 +707,7 @@ impl<const MDLEN: usize> OneWayHasher<MDLEN> for Shake256<MDLEN> {
 
     #[inline]
     fn reset(&mut self) -> &mut Self {
-        self.ctx.st = State { q: [0; 25] };
-        self.ctx.pt = 0;
+        self.ctx.clear();
         self.ctx.rsiz = 200 - (2 * 32);
         self
     }
@@ -412,12 +719,13 @@ impl<const MDLEN: usize> OneWayHasher<MDLEN> for Shake256<MDLEN> {
     }
 
     #[inline]
-    fn finish(&mut self, digest: &mut [u8; MDLEN]) {
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
         self.ctx.shake_xof();
         self.ctx.shake_out(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl<const MDLEN: usize> std::io::Write for Shake256<MDLEN> {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
         Context::update(&mut self.ctx, bytes);
@@ -428,3 +736,28 @@ impl<const MDLEN: usize> std::io::Write for Shake256<MDLEN> {
         Ok(())
     }
 }
+
+/// A finalized SHAKE context that can be squeezed incrementally. Consecutive reads continue the
+/// output stream where the previous one ended, so reading 10 bytes and then 20 bytes yields
+/// the same 30 bytes as a single call to ```finish_xof()```. ```B``` is the security strength
+/// in bytes (16 for SHAKE128 and 32 for SHAKE256).
+pub struct XofReader<const B: usize> {
+    ctx: Context<B, 0>,
+}
+
+impl<const B: usize> XofReader<B> {
+    /// Fills ```output``` with the next ```output.len()``` bytes of the output stream.
+    #[inline]
+    pub fn squeeze(&mut self, output: &mut [u8]) {
+        self.ctx.shake_out(output)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const B: usize> std::io::Read for XofReader<B> {
+    /// Always fills the entire buffer, since the output stream never ends.
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.ctx.shake_out(buf);
+        Ok(buf.len())
+    }
+}
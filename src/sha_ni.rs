@@ -0,0 +1,105 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use core::arch::x86_64::*;
+
+// https://www.intel.com/content/www/us/en/developer/articles/technical/intel-sha-extensions.html
+
+/// Returns true if the CPU supports the SHA extensions and the SSE instructions used alongside
+/// them. The result of the detection is cached by the standard library, so this is cheap to call.
+/// Without std this is decided at compile time.
+#[inline]
+pub(crate) fn is_available() -> bool {
+    #[cfg(feature = "std")]
+    {
+        cpu_feature!("sha") && cpu_feature!("sse2") && cpu_feature!("ssse3") && cpu_feature!("sse4.1")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(all(target_feature = "sha", target_feature = "sse2", target_feature = "ssse3", target_feature = "sse4.1"))
+    }
+}
+
+/// Calculates the next four words of the message schedule from the previous sixteen.
+#[inline(always)]
+unsafe fn schedule(w0: __m128i, w1: __m128i, w2: __m128i, w3: __m128i) -> __m128i {
+    let t: __m128i = _mm_add_epi32(_mm_sha256msg1_epu32(w0, w1), _mm_alignr_epi8(w3, w2, 4));
+    _mm_sha256msg2_epu32(t, w3)
+}
+
+/// Performs four rounds of the compression function. Each sha256rnds2 instruction performs two.
+macro_rules! rounds4 {
+    ($abef:ident, $cdgh:ident, $w:expr, $i:literal) => {
+        let k: __m128i = _mm_loadu_si128(crate::sha2::CONSTANTS_256.as_ptr().add($i * 4) as *const __m128i);
+        let t: __m128i = _mm_add_epi32($w, k);
+        $cdgh = _mm_sha256rnds2_epu32($cdgh, $abef, t);
+        $abef = _mm_sha256rnds2_epu32($abef, $cdgh, _mm_shuffle_epi32(t, 0x0e));
+    };
+}
+
+/// Extends the message schedule by four words and performs four rounds using them.
+macro_rules! schedule_rounds4 {
+    ($abef:ident, $cdgh:ident, $w0:ident, $w1:ident, $w2:ident, $w3:ident, $w4:ident, $i:literal) => {
+        $w4 = schedule($w0, $w1, $w2, $w3);
+        rounds4!($abef, $cdgh, $w4, $i);
+    };
+}
+
+/// Runs the SHA-256 compression function over each 64 byte chunk in ```chunks```. The length of
+/// ```chunks``` must be a multiple of 64. The caller must make sure ```is_available()``` is true.
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+pub(crate) unsafe fn compress(state: &mut [u32; 8], chunks: &[u8]) {
+    debug_assert!(chunks.len() % 64 == 0);
+
+    // reverses the bytes in each 32 bit word, since the message is big-endian
+    let mask: __m128i = _mm_set_epi64x(0x0c0d0e0f08090a0bu64 as i64, 0x0405060700010203u64 as i64);
+
+    // the rounds instruction expects the state as ABEF and CDGH rather than ABCD and EFGH
+    let dcba: __m128i = _mm_loadu_si128(state.as_ptr() as *const __m128i);
+    let efgh: __m128i = _mm_loadu_si128(state.as_ptr().add(4) as *const __m128i);
+    let cdab: __m128i = _mm_shuffle_epi32(dcba, 0xb1);
+    let hgfe: __m128i = _mm_shuffle_epi32(efgh, 0x1b);
+    let mut abef: __m128i = _mm_alignr_epi8(cdab, hgfe, 8);
+    let mut cdgh: __m128i = _mm_blend_epi16(hgfe, cdab, 0xf0);
+
+    for chunk in chunks.chunks_exact(64) {
+        let abef_save: __m128i = abef;
+        let cdgh_save: __m128i = cdgh;
+
+        let p: *const __m128i = chunk.as_ptr() as *const __m128i;
+        let mut w0: __m128i = _mm_shuffle_epi8(_mm_loadu_si128(p), mask);
+        let mut w1: __m128i = _mm_shuffle_epi8(_mm_loadu_si128(p.add(1)), mask);
+        let mut w2: __m128i = _mm_shuffle_epi8(_mm_loadu_si128(p.add(2)), mask);
+        let mut w3: __m128i = _mm_shuffle_epi8(_mm_loadu_si128(p.add(3)), mask);
+        let mut w4: __m128i;
+
+        rounds4!(abef, cdgh, w0, 0);
+        rounds4!(abef, cdgh, w1, 1);
+        rounds4!(abef, cdgh, w2, 2);
+        rounds4!(abef, cdgh, w3, 3);
+        schedule_rounds4!(abef, cdgh, w0, w1, w2, w3, w4, 4);
+        schedule_rounds4!(abef, cdgh, w1, w2, w3, w4, w0, 5);
+        schedule_rounds4!(abef, cdgh, w2, w3, w4, w0, w1, 6);
+        schedule_rounds4!(abef, cdgh, w3, w4, w0, w1, w2, 7);
+        schedule_rounds4!(abef, cdgh, w4, w0, w1, w2, w3, 8);
+        schedule_rounds4!(abef, cdgh, w0, w1, w2, w3, w4, 9);
+        schedule_rounds4!(abef, cdgh, w1, w2, w3, w4, w0, 10);
+        schedule_rounds4!(abef, cdgh, w2, w3, w4, w0, w1, 11);
+        schedule_rounds4!(abef, cdgh, w3, w4, w0, w1, w2, 12);
+        schedule_rounds4!(abef, cdgh, w4, w0, w1, w2, w3, 13);
+        schedule_rounds4!(abef, cdgh, w0, w1, w2, w3, w4, 14);
+        schedule_rounds4!(abef, cdgh, w1, w2, w3, w4, w0, 15);
+
+        abef = _mm_add_epi32(abef, abef_save);
+        cdgh = _mm_add_epi32(cdgh, cdgh_save);
+    }
+
+    // convert back to ABCD and EFGH
+    let feba: __m128i = _mm_shuffle_epi32(abef, 0x1b);
+    let dchg: __m128i = _mm_shuffle_epi32(cdgh, 0xb1);
+    let dcba: __m128i = _mm_blend_epi16(feba, dchg, 0xf0);
+    let hgef: __m128i = _mm_alignr_epi8(dchg, feba, 8);
+    _mm_storeu_si128(state.as_mut_ptr() as *mut __m128i, dcba);
+    _mm_storeu_si128(state.as_mut_ptr().add(4) as *mut __m128i, hgef);
+}
@@ -0,0 +1,417 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::digest::Digest;
+use crate::error::{Error, ErrorKind, Result};
+use crate::sha3::Context;
+use crate::{FinishXOF, OneWayHasher};
+use alloc::vec;
+use alloc::vec::Vec;
+
+// https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-185.pdf
+// https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+
+/// Encodes ```x``` as a byte string that may be unambiguously parsed from the beginning.
+pub fn left_encode(x: u64) -> Vec<u8> {
+    let bytes: [u8; 8] = x.to_be_bytes();
+    let skip: usize = (bytes.iter().take_while(|b| **b == 0).count()).min(7);
+    let mut encoded: Vec<u8> = vec![(8 - skip) as u8];
+    encoded.extend_from_slice(&bytes[skip..]);
+    encoded
+}
+
+/// Encodes ```x``` as a byte string that may be unambiguously parsed from the end.
+pub fn right_encode(x: u64) -> Vec<u8> {
+    let bytes: [u8; 8] = x.to_be_bytes();
+    let skip: usize = (bytes.iter().take_while(|b| **b == 0).count()).min(7);
+    let mut encoded: Vec<u8> = bytes[skip..].to_vec();
+    encoded.push((8 - skip) as u8);
+    encoded
+}
+
+/// Encodes a byte string so that it may be unambiguously parsed from the beginning.
+pub fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut encoded: Vec<u8> = left_encode(s.len() as u64 * 8);
+    encoded.extend_from_slice(s);
+    encoded
+}
+
+/// Prepends the encoding of ```w``` to ```x``` and pads the result with zeros to a multiple of ```w``` bytes.
+pub fn bytepad(x: &[u8], w: usize) -> Vec<u8> {
+    let mut padded: Vec<u8> = left_encode(w as u64);
+    // allocate once so that a KMAC key is never left behind in a freed buffer
+    padded.reserve((padded.len() + x.len()).div_ceil(w) * w - padded.len());
+    padded.extend_from_slice(x);
+    padded.resize(padded.len().div_ceil(w) * w, 0);
+    padded
+}
+
+/// The customizable SHAKE function. ```B``` is the security strength in bytes (16 or 32) and
+/// ```MDLEN``` is the length of the digest returned by ```finish()```.
+pub struct CShake<const B: usize, const MDLEN: usize> {
+    ctx: Context<B, MDLEN>,
+    prefix: Vec<u8>,
+}
+
+impl<const B: usize, const MDLEN: usize> CShake<B, MDLEN> {
+    const RATE: usize = 200 - (2 * B);
+
+    /// Returns a new context. ```function_name``` is reserved for functions defined by NIST and
+    /// should normally be empty, while ```customization``` is a user defined domain separation string.
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        Self::with_prefix(function_name, customization, &[])
+    }
+
+    /// Returns a new context that absorbs ```extra``` after the encoded function name and
+    /// customization string. This is how KMAC, TupleHash and ParallelHash build on cSHAKE.
+    fn with_prefix(function_name: &[u8], customization: &[u8], extra: &[u8]) -> Self {
+        let mut prefix: Vec<u8> = Vec::new();
+        if !function_name.is_empty() || !customization.is_empty() {
+            let mut x: Vec<u8> = encode_string(function_name);
+            x.extend_from_slice(&encode_string(customization));
+            prefix = bytepad(&x, Self::RATE);
+        }
+        prefix.extend_from_slice(extra);
+        let mut ctx: Self = Self {
+            ctx: Context::init(),
+            prefix,
+        };
+        ctx.reset();
+        ctx
+    }
+
+    /// Pads the state and prepares it for squeezing. When both the function name and the
+    /// customization string are empty, cSHAKE is defined to be equal to SHAKE.
+    fn pad(&mut self) {
+        if self.prefix.is_empty() {
+            self.ctx.shake_xof()
+        } else {
+            self.ctx.cshake_xof()
+        }
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> FinishXOF for CShake<B, MDLEN> {
+    fn finish_xof(&mut self, digest: &mut [u8]) {
+        self.pad();
+        self.ctx.shake_out(digest)
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> OneWayHasher<MDLEN> for CShake<B, MDLEN> {
+    const NAME: &'static str = if B == 16 { "cshake128" } else { "cshake256" };
+    const BLOCK_SIZE: usize = 200 - 2 * B;
+
+    /// Returns a context with an empty function name and customization string, which is
+    /// equivalent to SHAKE.
+    #[inline]
+    fn init() -> Self {
+        Self::new(&[], &[])
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx = Context::init();
+        self.ctx.update(&self.prefix);
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
+        self.finish_xof(digest);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const B: usize, const MDLEN: usize> std::io::Write for CShake<B, MDLEN> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Wipes the prefix, which holds the key in the case of KMAC. The sponge state wipes itself.
+#[cfg(feature = "zeroize")]
+impl<const B: usize, const MDLEN: usize> Drop for CShake<B, MDLEN> {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.prefix.as_mut_slice().zeroize();
+    }
+}
+
+pub type CShake128<const MDLEN: usize> = CShake<16, MDLEN>;
+pub type CShake256<const MDLEN: usize> = CShake<32, MDLEN>;
+
+/// Wipes a temporary copy of a KMAC key when the zeroize feature is enabled.
+#[inline]
+fn wipe_key(bytes: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(bytes);
+    #[cfg(not(feature = "zeroize"))]
+    let _ = bytes;
+}
+
+/// The Keccak message authentication code. ```finish()``` returns KMAC with an output length of
+/// ```MDLEN``` bytes, while ```finish_xof()``` returns KMACXOF of any length.
+pub struct Kmac<const B: usize, const MDLEN: usize> {
+    cshake: CShake<B, MDLEN>,
+}
+
+impl<const B: usize, const MDLEN: usize> Kmac<B, MDLEN> {
+    pub fn new(key: &[u8], customization: &[u8]) -> Self {
+        let mut encoded_key: Vec<u8> = encode_string(key);
+        let mut padded_key: Vec<u8> = bytepad(&encoded_key, CShake::<B, MDLEN>::RATE);
+        let kmac: Self = Self {
+            cshake: CShake::with_prefix(b"KMAC", customization, &padded_key),
+        };
+        wipe_key(&mut encoded_key);
+        wipe_key(&mut padded_key);
+        kmac
+    }
+
+    /// Calculates the tag and compares it to ```tag``` in constant time. Returns true if they are
+    /// equal. Tags whose length is not equal to ```MDLEN``` are always rejected.
+    pub fn verify(&mut self, tag: &[u8]) -> bool {
+        let expected: Digest<MDLEN> = self.finish();
+        expected.ct_eq(tag)
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> FinishXOF for Kmac<B, MDLEN> {
+    fn finish_xof(&mut self, digest: &mut [u8]) {
+        self.cshake.update(&right_encode(0)).finish_xof(digest)
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> OneWayHasher<MDLEN> for Kmac<B, MDLEN> {
+    const NAME: &'static str = if B == 16 { "kmac128" } else { "kmac256" };
+    const BLOCK_SIZE: usize = 200 - 2 * B;
+
+    /// Returns a context with an empty key and customization string. Use ```Kmac::new()``` to
+    /// supply a key.
+    #[inline]
+    fn init() -> Self {
+        Self::new(&[], &[])
+    }
+
+    /// Resets the context to its initial state while keeping the key.
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.cshake.reset();
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.cshake.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
+        self.cshake.update(&right_encode(MDLEN as u64 * 8)).finish_xof(digest);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const B: usize, const MDLEN: usize> std::io::Write for Kmac<B, MDLEN> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+pub type Kmac128<const MDLEN: usize> = Kmac<16, MDLEN>;
+pub type Kmac256<const MDLEN: usize> = Kmac<32, MDLEN>;
+
+/// Hashes a tuple of byte strings such that the boundaries between them are part of the hash.
+/// Each call to ```push_element()``` absorbs exactly one element of the tuple, so ```("ab", "c")```
+/// and ```("a", "bc")``` produce different digests. ```finish_xof()``` returns TupleHashXOF.
+///
+/// Since the way the input is split matters, TupleHash does not implement ```OneWayHasher``` or
+/// ```std::io::Write```, which are free to split a stream of bytes however they like.
+pub struct TupleHash<const B: usize, const MDLEN: usize> {
+    cshake: CShake<B, MDLEN>,
+}
+
+impl<const B: usize, const MDLEN: usize> TupleHash<B, MDLEN> {
+    pub fn new(customization: &[u8]) -> Self {
+        Self {
+            cshake: CShake::with_prefix(b"TupleHash", customization, &[]),
+        }
+    }
+
+    /// Resets the context to its initial state while keeping the customization string.
+    #[inline]
+    pub fn reset(&mut self) -> &mut Self {
+        self.cshake.reset();
+        self
+    }
+
+    /// Absorbs ```element``` as the next element of the tuple.
+    #[inline]
+    pub fn push_element(&mut self, element: &[u8]) -> &mut Self {
+        self.cshake.update(&encode_string(element));
+        self
+    }
+
+    /// Finalizes the hash and writes the digest to ```digest```. Call ```reset()``` before using
+    /// the context again.
+    #[inline]
+    pub fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
+        self.cshake.update(&right_encode(MDLEN as u64 * 8)).finish_xof(digest);
+    }
+
+    /// Finalizes the hash and returns the digest. Call ```reset()``` before using the context again.
+    #[inline]
+    pub fn finish(&mut self) -> Digest<MDLEN> {
+        let mut digest: Digest<MDLEN> = Digest::new();
+        self.finish_into(&mut digest.0);
+        digest
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> FinishXOF for TupleHash<B, MDLEN> {
+    fn finish_xof(&mut self, digest: &mut [u8]) {
+        self.cshake.update(&right_encode(0)).finish_xof(digest)
+    }
+}
+
+pub type TupleHash128<const MDLEN: usize> = TupleHash<16, MDLEN>;
+pub type TupleHash256<const MDLEN: usize> = TupleHash<32, MDLEN>;
+
+/// Splits the input into blocks of ```block_size``` bytes, hashes each block independently, and
+/// then hashes the concatenated results. ```finish_xof()``` returns ParallelHashXOF.
+pub struct ParallelHash<const B: usize, const MDLEN: usize> {
+    cshake: CShake<B, MDLEN>,
+    block_size: usize,
+    buf: Vec<u8>,
+    blocks: u64,
+}
+
+impl<const B: usize, const MDLEN: usize> ParallelHash<B, MDLEN> {
+    /// The block size used by ```init()```.
+    pub const DEFAULT_BLOCK_SIZE: usize = 8192;
+
+    pub fn new(block_size: usize, customization: &[u8]) -> Result<Self> {
+        if block_size == 0 {
+            Err(Error::new(ErrorKind::ZeroBlockSize, "The block size must be greater than zero."))
+        } else {
+            Ok(Self {
+                cshake: CShake::with_prefix(b"ParallelHash", customization, &left_encode(block_size as u64)),
+                block_size,
+                buf: Vec::with_capacity(block_size),
+                blocks: 0,
+            })
+        }
+    }
+
+    /// Hashes a single block with SHAKE and absorbs the 2 * ```B``` byte result.
+    fn absorb_block(&mut self, block: &[u8]) {
+        let mut inner: Context<B, 0> = Context::init();
+        let mut digest: [u8; 64] = [0; 64];
+        inner.update(block);
+        inner.shake_xof();
+        inner.shake_out(&mut digest[..(2 * B)]);
+        self.cshake.update(&digest[..(2 * B)]);
+        self.blocks += 1;
+    }
+
+    /// Absorbs any partial block that remains in the buffer, followed by the block count and
+    /// the encoded output length.
+    fn wrap_up(&mut self, output_bits: u64) {
+        if !self.buf.is_empty() {
+            let buf: Vec<u8> = core::mem::take(&mut self.buf);
+            self.absorb_block(&buf);
+        }
+        let blocks: u64 = self.blocks;
+        self.cshake.update(&right_encode(blocks)).update(&right_encode(output_bits));
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> FinishXOF for ParallelHash<B, MDLEN> {
+    fn finish_xof(&mut self, digest: &mut [u8]) {
+        self.wrap_up(0);
+        self.cshake.finish_xof(digest)
+    }
+}
+
+impl<const B: usize, const MDLEN: usize> OneWayHasher<MDLEN> for ParallelHash<B, MDLEN> {
+    const NAME: &'static str = if B == 16 { "parallelhash128" } else { "parallelhash256" };
+    const BLOCK_SIZE: usize = 200 - 2 * B;
+
+    /// Returns a context with a block size of ```DEFAULT_BLOCK_SIZE``` bytes and an empty
+    /// customization string.
+    #[inline]
+    fn init() -> Self {
+        // the default block size is never zero
+        Self::new(Self::DEFAULT_BLOCK_SIZE, &[]).unwrap()
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.cshake.reset();
+        self.buf.clear();
+        self.blocks = 0;
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, mut data: &[u8]) -> &mut Self {
+        while !data.is_empty() {
+            if self.buf.is_empty() && data.len() >= self.block_size {
+                // hash whole blocks directly from the input without copying them
+                let (block, rest) = data.split_at(self.block_size);
+                self.absorb_block(block);
+                data = rest;
+            } else {
+                let len: usize = (self.block_size - self.buf.len()).min(data.len());
+                self.buf.extend_from_slice(&data[..len]);
+                data = &data[len..];
+                if self.buf.len() == self.block_size {
+                    let buf: Vec<u8> = core::mem::take(&mut self.buf);
+                    self.absorb_block(&buf);
+                    self.buf = buf;
+                    self.buf.clear();
+                }
+            }
+        }
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; MDLEN]) {
+        self.wrap_up(MDLEN as u64 * 8);
+        self.cshake.finish_xof(digest);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const B: usize, const MDLEN: usize> std::io::Write for ParallelHash<B, MDLEN> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+pub type ParallelHash128<const MDLEN: usize> = ParallelHash<16, MDLEN>;
+pub type ParallelHash256<const MDLEN: usize> = ParallelHash<32, MDLEN>;
@@ -0,0 +1,145 @@
+// Copyright (c) 2023 herrsmitty8128
+// Distributed under the MIT software license, see the accompanying
+// file LICENSE.txt or http://www.opensource.org/licenses/mit-license.php.
+
+use crate::error::Result;
+use crate::sha2::{Midstate256, Sha256};
+use crate::OneWayHasher;
+use core::marker::PhantomData;
+
+// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#design
+// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki
+
+/// A tag for BIP340 tagged hashes. ```MIDSTATE``` is the state of Sha256 after absorbing the
+/// 64 byte prefix ```SHA256(NAME) || SHA256(NAME)```, so that it only has to be calculated once.
+pub trait Tag {
+    /// The name of the tag.
+    const NAME: &'static str;
+
+    /// The midstate of the tag prefix. See ```tag_midstate()```.
+    const MIDSTATE: Midstate256;
+}
+
+/// Defines a marker type that implements ```Tag```.
+macro_rules! tag {
+    ($(#[$doc:meta])* $name:ident, $tag:literal, [$($w:literal),*]) => {
+        $(#[$doc])*
+        pub struct $name;
+
+        impl Tag for $name {
+            const NAME: &'static str = $tag;
+            const MIDSTATE: Midstate256 = Midstate256 { state: [$($w),*], len: 64 };
+        }
+    };
+}
+
+tag!(
+    /// The tag used for the challenge of a BIP340 Schnorr signature.
+    Bip340Challenge, "BIP0340/challenge",
+    [0x9cecba11, 0x23925381, 0x11679112, 0xd1627e0f, 0x97c87550, 0x003cc765, 0x90f61164, 0x33e9b66a]
+);
+
+tag!(
+    /// The tag used to hash the auxiliary randomness when signing with BIP340.
+    Bip340Aux, "BIP0340/aux",
+    [0x24dd3219, 0x4eba7e70, 0xca0fabb9, 0x0fa3166d, 0x3afbe4b1, 0x4c44df97, 0x4aac2739, 0x249e850a]
+);
+
+tag!(
+    /// The tag used to derive the nonce when signing with BIP340.
+    Bip340Nonce, "BIP0340/nonce",
+    [0x46615b35, 0xf4bfbff7, 0x9f8dc671, 0x83627ab3, 0x60217180, 0x57358661, 0x21a29e54, 0x68b07b4c]
+);
+
+tag!(
+    /// The tag used for the leaves of a Taproot script tree.
+    TapLeaf, "TapLeaf",
+    [0x9ce0e4e6, 0x7c116c39, 0x38b3caf2, 0xc30f5089, 0xd3f3936c, 0x47636e60, 0x7db33eea, 0xddc6f0c9]
+);
+
+tag!(
+    /// The tag used for the branches of a Taproot script tree.
+    TapBranch, "TapBranch",
+    [0x23a865a9, 0xb8a40da7, 0x977c1e04, 0xc49e246f, 0xb5be1376, 0x9d24c9b7, 0xb583b5d4, 0xa8d226d2]
+);
+
+tag!(
+    /// The tag used to tweak a Taproot internal key.
+    TapTweak, "TapTweak",
+    [0xd129a2f3, 0x701c655d, 0x6583b6c3, 0xb9419727, 0x95f4e232, 0x94fd54f4, 0xa2ae8d85, 0x47ca590b]
+);
+
+tag!(
+    /// The tag used for Taproot signature hashes.
+    TapSighash, "TapSighash",
+    [0xf504a425, 0xd7f8783b, 0x1363868a, 0xe3e55658, 0x6eee945d, 0xbc7888dd, 0x02a6e2c3, 0x1873fe9f]
+);
+
+/// Calculates the midstate of Sha256 after absorbing ```SHA256(tag) || SHA256(tag)```. This is
+/// how the midstates of the standard tags were calculated, and can be used with
+/// ```Sha256::from_midstate()``` to hash with any other tag.
+pub fn tag_midstate(tag: &[u8]) -> Result<Midstate256> {
+    let mut tag_hash: [u8; 32] = [0; 32];
+    let mut ctx: Sha256 = Sha256::init();
+    ctx.update(tag).finish_into(&mut tag_hash);
+    ctx.reset().update(&tag_hash).update(&tag_hash).midstate()
+}
+
+/// The BIP340 tagged hash ```SHA256(SHA256(tag) || SHA256(tag) || msg)```, where the tag is one of
+/// the marker types that implement ```Tag```. Hashing starts from the precomputed midstate of the
+/// tag, so the prefix is never hashed again.
+pub struct TaggedHash<T: Tag> {
+    ctx: Sha256,
+    _t: PhantomData<T>,
+}
+
+pub type Bip340ChallengeHash = TaggedHash<Bip340Challenge>;
+pub type Bip340AuxHash = TaggedHash<Bip340Aux>;
+pub type Bip340NonceHash = TaggedHash<Bip340Nonce>;
+pub type TapLeafHash = TaggedHash<TapLeaf>;
+pub type TapBranchHash = TaggedHash<TapBranch>;
+pub type TapTweakHash = TaggedHash<TapTweak>;
+pub type TapSighashHash = TaggedHash<TapSighash>;
+
+impl<T: Tag> OneWayHasher<32> for TaggedHash<T> {
+    const NAME: &'static str = T::NAME;
+    const BLOCK_SIZE: usize = 64;
+
+    #[inline]
+    fn init() -> Self {
+        // the midstates of the tags are whole chunks, so this cannot fail
+        Self {
+            ctx: Sha256::from_midstate(&T::MIDSTATE).unwrap(),
+            _t: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn reset(&mut self) -> &mut Self {
+        self.ctx.set_midstate(&T::MIDSTATE).unwrap();
+        self
+    }
+
+    #[inline]
+    fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.ctx.update(data);
+        self
+    }
+
+    #[inline]
+    fn finish_into(&mut self, digest: &mut [u8; 32]) {
+        self.ctx.finish_into(digest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Tag> std::io::Write for TaggedHash<T> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::blake2::{Blake2b, Blake2s};
+    use bc_hash::digest::Digest;
+    use bc_hash::OneWayHasher;
+    use std::{error::Error, io::Write, str::FromStr};
+
+    #[test]
+    fn blake2b_vectors() -> Result<(), Box<dyn Error>> {
+        // RFC 7693 Appendix A
+        let mut digest: Digest<64> = Digest::new();
+        let mut ctx: Blake2b<64> = Blake2b::init();
+        ctx.update(b"abc").finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+                     7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+                )?,
+            "Blake2b-512 of \"abc\" failed"
+        );
+
+        let mut digest: Digest<32> = Digest::new();
+        Blake2b::<32>::init().finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8")?,
+            "Blake2b-256 of the empty string failed"
+        );
+
+        // keyed hashing from the reference implementation's test vectors
+        let key: Vec<u8> = (0..64).collect();
+        let data: Vec<u8> = (0..255).collect();
+        let mut digest: Digest<64> = Digest::new();
+        let mut ctx: Blake2b<64> = Blake2b::with_key(&key)?;
+        ctx.finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "10ebb67700b1868efb4417987acf4690ae9d972fb7a590c2f02871799aaa4786\
+                     b5e996e8f0f4eb981fc214b005f42d2ff4233499391653df7aefcbc13fc51568"
+                )?,
+            "Keyed Blake2b of the empty string failed"
+        );
+        ctx.reset().update(&data[..128]).finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "72065ee4dd91c2d8509fa1fc28a37c7fc9fa7d5b3f8ad3d0d7a25626b57b1b44\
+                     788d4caf806290425f9890a3a2a35a905ab4b37acfd0da6e4517b2525c9651e4"
+                )?,
+            "Keyed Blake2b of one full block failed"
+        );
+        ctx.reset();
+        for chunk in data.chunks(7) {
+            ctx.write_all(chunk)?;
+        }
+        ctx.finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e9248\
+                     4be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461"
+                )?,
+            "Keyed Blake2b of 255 bytes failed"
+        );
+
+        // key, salt and personalization with a truncated digest
+        let mut digest: Digest<20> = Digest::new();
+        Blake2b::<20>::with_params(b"secret", b"saltsalt", b"ZcashPoW")?
+            .update(b"hello world")
+            .finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("37699e002239b61f12f77fc5ba796acaaf5dce72")?,
+            "Blake2b with salt and personalization failed"
+        );
+
+        assert!(Blake2b::<64>::with_key(&[0; 65]).is_err(), "Blake2b accepted a key that is too long");
+        assert!(Blake2b::<64>::with_params(&[], &[0; 17], &[]).is_err(), "Blake2b accepted a salt that is too long");
+
+        Ok(())
+    }
+
+    #[test]
+    fn blake2s_vectors() -> Result<(), Box<dyn Error>> {
+        // RFC 7693 Appendix B
+        let mut digest: Digest<32> = Digest::new();
+        let mut ctx: Blake2s<32> = Blake2s::init();
+        ctx.update(b"abc").finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982")?,
+            "Blake2s-256 of \"abc\" failed"
+        );
+
+        let key: Vec<u8> = (0..32).collect();
+        let data: Vec<u8> = (0..255).collect();
+        Blake2s::<32>::with_key(&key)?.update(&data).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd")?,
+            "Keyed Blake2s of 255 bytes failed"
+        );
+
+        let mut digest: Digest<16> = Digest::new();
+        Blake2s::<16>::with_params(b"secret", b"salt", b"personal")?
+            .update(b"hello world")
+            .finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("26b31409685f810b20829ae480fb49f1")?,
+            "Blake2s with salt and personalization failed"
+        );
+
+        assert!(Blake2s::<32>::with_key(&[0; 33]).is_err(), "Blake2s accepted a key that is too long");
+        assert!(Blake2s::<32>::with_params(&[], &[], &[0; 9]).is_err(), "Blake2s accepted a personalization that is too long");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::blake3::Blake3;
+    use bc_hash::digest::Digest;
+    use bc_hash::{FinishXOF, OneWayHasher};
+    use std::{error::Error, io::Write, str::FromStr};
+
+    const KEY: &[u8; 32] = b"whats the Elephant we're testing";
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    /// (input length, hash, keyed hash, derived key) where the input is the repeating sequence
+    /// 0, 1, ... 250 used by the official BLAKE3 test vectors
+    const VECTORS: [(usize, &str, &str, &str); 10] = [
+        (
+            0,
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            "ba4211a201d60b16233f306f2dbcc0534a862a2f2e947e14bb06a33227ce2bf4",
+            "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d",
+        ),
+        (
+            1,
+            "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213",
+            "db8900643405e6588db5e088e3cf40a67fbc9892dac7a29cfcaea9978804a94a",
+            "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c",
+        ),
+        (
+            1023,
+            "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11",
+            "244139228e19fb9013608ab2908a4d710b4088dc1eff3d30ba2cb34619af956b",
+            "74a16c1c3d44368a86e1ca6df64be6a2f64cce8f09220787450722d85725dea5",
+        ),
+        (
+            1024,
+            "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7",
+            "4a94ab708c22059cc8e7e129d337c87da0e7c1c158fbcc850bfd8663c78aa9aa",
+            "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706",
+        ),
+        (
+            1025,
+            "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444",
+            "2d338e7553b4fb812762dc407239f0db99d0a10b0aac1e1e6a92ab95139cfdc8",
+            "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb",
+        ),
+        (
+            2049,
+            "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030",
+            "18b01d085d050ee5fca3b3c7fff5961af2879bac91dae0de926c8ea17bb660e7",
+            "2ea477c5515cc3dd606512ee72bb3e0e758cfae7232826f35fb98ca1bcbdf273",
+        ),
+        (
+            3072,
+            "b98cb0ff3623be03326b373de6b9095218513e64f1ee2edd2525c7ad1e5cffd2",
+            "6e8f522977cc4a4c26760333d5d56b2e9d10f0f0f5e1a2ed6bd97b7122ad6cdf",
+            "050df97f8c2ead654d9bb3ab8c9178edcd902a32f8495949feadcc1e0480c46b",
+        ),
+        (
+            8193,
+            "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b",
+            "e54c95ac110628c3505319634215e291d9631bfaef7ff636e8aa129ea6b52815",
+            "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1",
+        ),
+        (
+            31744,
+            "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47",
+            "eb335870420bdc546710cc7e09af48a964ae1aefadddf16605c0d057729288bc",
+            "39772aef80e0ebe60596361e45b061e8f417429d529171b6764468c22928e28e",
+        ),
+        (
+            102400,
+            "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085",
+            "e78a54a208f4e96dc13cf8b65602a9f4a8b294b6b5fd58112658d5db4715cb28",
+            "4652cff7a3f385a6103b5c260fc1593e13c778dbe608efb092fe7ee69df6e9c6",
+        ),
+    ];
+
+    fn input(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn blake3_vectors() -> Result<(), Box<dyn Error>> {
+        let mut digest: Digest<32> = Digest::new();
+        let mut hasher: Blake3 = Blake3::init();
+        let mut keyed: Blake3 = Blake3::with_key(KEY);
+        let mut derive: Blake3 = Blake3::derive_key(CONTEXT);
+        for (len, hash, keyed_hash, derived_key) in VECTORS {
+            let data: Vec<u8> = input(len);
+
+            hasher.reset().update(&data).finish_into(&mut digest.0);
+            assert!(digest == Digest::from_str(hash)?, "Blake3 failed for length {}", len);
+
+            keyed.reset().update(&data).finish_into(&mut digest.0);
+            assert!(digest == Digest::from_str(keyed_hash)?, "Keyed Blake3 failed for length {}", len);
+
+            derive.reset().update(&data).finish_into(&mut digest.0);
+            assert!(digest == Digest::from_str(derived_key)?, "Blake3 key derivation failed for length {}", len);
+
+            // writing in uneven pieces must not change the result
+            hasher.reset();
+            for chunk in data.chunks(333) {
+                hasher.write_all(chunk)?;
+            }
+            hasher.finish_into(&mut digest.0);
+            assert!(digest == Digest::from_str(hash)?, "Incremental Blake3 failed for length {}", len);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn blake3_xof() -> Result<(), Box<dyn Error>> {
+        let expected: Digest<131> = Digest::from_str(
+            "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444\
+             f4c4a22b4b399155358a994e52bf255de60035742ec71bd08ac275a1b51cc6bf\
+             e332b0ef84b409108cda080e6269ed4b3e2c3f7d722aa4cdc98d16deb554e562\
+             7be8f955c98e1d5f9565a9194cad0c4285f93700062d9595adb992ae68ff1280\
+             0ab67a",
+        )?;
+        let mut digest: Digest<131> = Digest::new();
+        Blake3::init().update(&input(1025)).finish_xof(&mut digest.0);
+        assert!(digest == expected, "Blake3 extendable output failed");
+        Ok(())
+    }
+
+    #[test]
+    fn blake3_parallel() -> Result<(), Box<dyn Error>> {
+        let mut expected: Digest<32> = Digest::new();
+        let mut digest: Digest<32> = Digest::new();
+        for (len, _, _, _) in VECTORS {
+            let data: Vec<u8> = input(len);
+            for hasher in [Blake3::init(), Blake3::with_key(KEY), Blake3::derive_key(CONTEXT)].iter_mut() {
+                hasher.update(&data).finish_into(&mut expected.0);
+                for threads in [1, 2, 3, 8] {
+                    hasher.hash_parallel(&data, threads, &mut digest.0);
+                    assert!(digest == expected, "Parallel Blake3 failed for length {} with {} threads", len, threads);
+                }
+            }
+        }
+        let mut xof: Digest<131> = Digest::new();
+        Blake3::init().hash_parallel(&input(1025), 4, &mut xof.0);
+        assert!(xof.to_string().starts_with(VECTORS[4].1), "Parallel Blake3 extendable output failed");
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::{self, Digest};
+    use bc_hash::merkle::{compute_proof, compute_root, verify_proof, Proof};
+    use bc_hash::sha2::{Sha256, Sha256d};
+    use bc_hash::OneWayHasher;
+    use std::error::Error;
+
+    #[test]
+    fn digest_ct_eq() -> Result<(), Box<dyn Error>> {
+        let a: Digest<32> = Sha256::digest(b"abc");
+        let mut b: Digest<32> = a.clone();
+        assert!(a.ct_eq(&b.0) && a.ct_eq(b.as_slice()), "Equal digests compared unequal");
+        b[31] ^= 1;
+        assert!(!a.ct_eq(&b.0), "Digests that differ in the last byte compared equal");
+        assert!(!a.ct_eq(&a.0[..31]), "A truncated digest compared equal");
+        assert!(digest::ct_eq(&[], &[]), "Empty slices compared unequal");
+        assert!(!digest::ct_eq(&[0], &[]), "Slices of different lengths compared equal");
+        Ok(())
+    }
+
+    #[test]
+    fn merkle_verify_proof() -> Result<(), Box<dyn Error>> {
+        let leaves: Vec<[u8; 32]> = (0..7u8).map(|i| Sha256::digest([i]).0).collect();
+        let mut root: Vec<[u8; 32]> = leaves.clone();
+        compute_root::<32, Sha256d>(&mut root)?;
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let (proof, _) = compute_proof::<32, Sha256d>(&mut leaves.clone(), index)?;
+            assert!(verify_proof::<32, Sha256d>(proof.clone(), leaf, &root[0]), "Proof of leaf {} failed", index);
+
+            // the proof must not verify a different leaf, a different root, or with another hasher
+            assert!(!verify_proof::<32, Sha256d>(proof.clone(), &[0; 32], &root[0]), "Proved the wrong leaf");
+            assert!(!verify_proof::<32, Sha256d>(proof.clone(), leaf, &[0; 32]), "Proved the wrong root");
+            assert!(!verify_proof::<32, Sha256>(proof, leaf, &root[0]), "Proved with the wrong hasher");
+        }
+
+        let empty: Proof<32> = Proof::new();
+        assert!(verify_proof::<32, Sha256d>(empty, &leaves[0], &leaves[0]), "A single leaf is its own root");
+        Ok(())
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_and_reset() -> Result<(), Box<dyn Error>> {
+        use zeroize::Zeroize;
+
+        let mut digest: Digest<32> = Sha256::digest(b"secret");
+        digest.zeroize();
+        assert!(digest == [0; 32], "Digest::zeroize() failed");
+
+        // wiping on reset must leave the hashers in their initial state
+        let expected: Digest<32> = Sha256::digest(b"abc");
+        let mut ctx: Sha256 = Sha256::init();
+        ctx.update(b"secret key material");
+        assert!(ctx.reset().update(b"abc").finish() == expected, "Sha256 after reset failed");
+        let expected: Digest<32> = bc_hash::sha3::Sha3_256::digest(b"abc");
+        let mut ctx: bc_hash::sha3::Sha3_256 = bc_hash::sha3::Sha3_256::init();
+        ctx.update(b"secret key material");
+        assert!(ctx.reset().update(b"abc").finish() == expected, "Sha3_256 after reset failed");
+        Ok(())
+    }
+}
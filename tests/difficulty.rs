@@ -0,0 +1,237 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::difficulty::{Asert, AsertAnchor, BitcoinRetarget, Header, Lwma, Retarget};
+    use std::error::Error;
+
+    /// Returns ```count``` consecutive headers starting at ```height```, ```spacing``` seconds apart.
+    fn headers(height: u64, count: u64, time: i64, spacing: i64, bits: u32) -> Vec<Header> {
+        (0..count)
+            .map(|i| Header {
+                height: height + i,
+                time: time + spacing * i as i64,
+                bits,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bitcoin_retarget() -> Result<(), Box<dyn Error>> {
+        let mainnet: BitcoinRetarget = BitcoinRetarget::mainnet();
+        assert!(mainnet.interval() == 2016, "The mainnet interval is not 2016 blocks");
+
+        // vectors from Bitcoin Core's pow_tests, as (height, time, bits, first block time, expected)
+        let vectors: [(u64, i64, u32, i64, u32); 4] = [
+            (32255, 1262152739, 0x1d00ffff, 1261130161, 0x1d00d86a),
+            (2015, 1233061996, 0x1d00ffff, 1231006505, 0x1d00ffff),
+            (68543, 1279297671, 0x1c05a3f4, 1279008237, 0x1c0168fd),
+            (46367, 1269211443, 0x1c387f6f, 1263163443, 0x1d00e1fd),
+        ];
+        for (height, time, bits, first_time, expected) in vectors {
+            let last: Header = Header { height, time, bits };
+            let next: u32 = mainnet.calculate_next_bits(&last, first_time)?;
+            assert!(next == expected, "Retarget at height {} failed: {:08x}", height, next);
+        }
+
+        // the target only changes at the end of an interval
+        let chain: Vec<Header> = headers(0, 2016, 1231006505, 300, 0x1c0ffff0);
+        assert!(mainnet.next_bits(&chain[..2000], 0)? == 0x1c0ffff0, "The target changed within an interval");
+        // the interval is measured over 2015 blocks, so blocks twice as fast slightly more than halve it
+        assert!(mainnet.next_bits(&chain, 0)? == 0x1c07fef3, "Blocks twice as fast did not halve the target");
+        assert!(mainnet.next_bits(&chain[1..], 0).is_err(), "Retargeted without the whole interval");
+        assert!(mainnet.next_bits(&[], 0)? == 0x1d00ffff, "The genesis block does not use the limit");
+
+        let gap: Vec<Header> = vec![chain[0], chain[2]];
+        assert!(mainnet.next_bits(&gap, 0).is_err(), "Accepted headers that are not consecutive");
+        Ok(())
+    }
+
+    #[test]
+    fn testnet_min_difficulty() -> Result<(), Box<dyn Error>> {
+        let testnet: BitcoinRetarget = BitcoinRetarget::testnet();
+        let mut chain: Vec<Header> = headers(4032, 10, 1_000_000, 600, 0x1c0ffff0);
+
+        // a block more than 20 minutes after its parent may use the limit
+        let last: Header = chain[9];
+        assert!(testnet.next_bits(&chain, last.time + 1201)? == 0x1d00ffff, "The 20 minute rule failed");
+        assert!(testnet.next_bits(&chain, last.time + 1200)? == 0x1c0ffff0, "The 20 minute rule applied too early");
+
+        // the blocks after a run of minimum difficulty blocks go back to the real target
+        for i in 0..3 {
+            chain.push(Header {
+                height: last.height + 1 + i,
+                time: last.time + 1201 * (i as i64 + 1),
+                bits: 0x1d00ffff,
+            });
+        }
+        let time: i64 = chain.last().unwrap().time + 60;
+        assert!(testnet.next_bits(&chain, time)? == 0x1c0ffff0, "Going back to the real target failed");
+        assert!(testnet.next_bits(&chain[10..], time).is_err(), "Went back past the headers given");
+
+        // the first block of an interval is never skipped
+        let boundary: Vec<Header> = headers(4032, 3, 1_000_000, 1201, 0x1d00ffff);
+        assert!(testnet.next_bits(&boundary, 1_002_500)? == 0x1d00ffff, "Went back past the start of the interval");
+        Ok(())
+    }
+
+    #[test]
+    fn asert() -> Result<(), Box<dyn Error>> {
+        let mut asert: Asert = Asert::bitcoin_cash();
+        let anchor: AsertAnchor = AsertAnchor {
+            height: 100,
+            parent_time: 1_000_000,
+            bits: 0x1c010000,
+        };
+        asert.anchor = anchor;
+        let half_life: i64 = asert.half_life;
+        let parent = |time: i64| Header {
+            height: 100,
+            time: anchor.parent_time + 600 + time,
+            bits: anchor.bits,
+        };
+
+        // on schedule the target stays the same, and it doubles or halves every half life
+        assert!(asert.calculate_next_bits(&parent(0))? == 0x1c010000, "On schedule failed");
+        assert!(asert.calculate_next_bits(&parent(half_life))? == 0x1c020000, "One half life behind failed");
+        assert!(asert.calculate_next_bits(&parent(-half_life))? == 0x1c008000, "One half life ahead failed");
+
+        // half a half life multiplies by the approximation of 2^0.5 (92674 / 65536)
+        assert!(asert.calculate_next_bits(&parent(half_life / 2))? == 0x1c016a02, "Half a half life behind failed");
+
+        // the target is clamped to the limit and to one
+        assert!(asert.calculate_next_bits(&parent(100 * half_life))? == 0x1d00ffff, "The limit was exceeded");
+        assert!(asert.calculate_next_bits(&parent(-300 * half_life))? == 0x01010000, "The target fell below one");
+
+        // a chain that stays on schedule keeps the anchor target
+        let chain: Vec<Header> = headers(100, 50, anchor.parent_time + 600, 600, anchor.bits);
+        assert!(asert.next_bits(&chain, 0)? == anchor.bits, "A chain on schedule changed the target");
+        assert!(asert.next_bits(&headers(50, 2, 0, 600, anchor.bits), 0).is_err(), "Retargeted before the anchor");
+        Ok(())
+    }
+
+    #[test]
+    fn lwma() -> Result<(), Box<dyn Error>> {
+        let lwma: Lwma = Lwma {
+            pow_limit: BitcoinRetarget::mainnet().pow_limit,
+            window: 45,
+            target_spacing: 120,
+        };
+
+        // until the chain is longer than the window the limit is used
+        assert!(lwma.next_bits(&headers(0, 45, 0, 120, 0x1c010000), 0)? == 0x1d00ffff, "A short chain did not use the limit");
+
+        // the average target is scaled by the weighted solve times, rounding down a little
+        assert!(lwma.next_bits(&headers(0, 46, 0, 120, 0x1c010000), 0)? == 0x1c00ffff, "On schedule failed");
+        assert!(lwma.next_bits(&headers(0, 46, 0, 60, 0x1c010000), 0)? == 0x1b7fffff, "Twice as fast failed");
+        assert!(lwma.next_bits(&headers(0, 46, 0, 240, 0x1c010000), 0)? == 0x1c01ffff, "Twice as slow failed");
+        assert!(lwma.next_bits(&headers(0, 45, 0, 120, 0x1c010000)[1..], 0)? == 0x1d00ffff, "Short window");
+        assert!(lwma.next_bits(&headers(100, 45, 0, 120, 0x1c010000), 0).is_err(), "Retargeted without the whole window");
+
+        // solve times are capped at six times the spacing, and timestamps that go backwards count as one second
+        let mut chain: Vec<Header> = headers(0, 46, 0, 120, 0x1c010000);
+        chain[45].time += 1_000_000;
+        let capped: u32 = lwma.next_bits(&chain, 0)?;
+        chain[45].time = chain[44].time + 6 * 120;
+        assert!(capped == lwma.next_bits(&chain, 0)?, "The solve time was not capped");
+        chain[45].time = chain[44].time - 1_000;
+        let backwards: u32 = lwma.next_bits(&chain, 0)?;
+        chain[45].time = chain[44].time + 1;
+        assert!(backwards == lwma.next_bits(&chain, 0)?, "A negative solve time did not count as one second");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_parameters() -> Result<(), Box<dyn Error>> {
+        let chain: Vec<Header> = headers(0, 2016, 1231006505, 600, 0x1c0ffff0);
+        let last: Header = chain[2015];
+
+        // parameters that would divide by zero, underflow or make clamp() panic are rejected
+        for (target_timespan, target_spacing) in [(1209600, 0), (300, 600), (-1209600, 600), (i64::MAX, 600)] {
+            let bitcoin: BitcoinRetarget = BitcoinRetarget {
+                target_timespan,
+                target_spacing,
+                ..BitcoinRetarget::mainnet()
+            };
+            assert!(bitcoin.interval() == 0, "Invalid parameters have an interval");
+            assert!(bitcoin.next_bits(&chain, 0).is_err(), "Accepted a timespan of {} and spacing of {}", target_timespan, target_spacing);
+            assert!(bitcoin.calculate_next_bits(&last, 0).is_err(), "Accepted a timespan of {} and spacing of {}", target_timespan, target_spacing);
+        }
+
+        // anchor targets above the limit would overflow the fixed point arithmetic
+        let mut asert: Asert = Asert::bitcoin_cash();
+        let parent: Header = Header {
+            height: asert.anchor.height,
+            time: asert.anchor.parent_time + 600,
+            bits: asert.anchor.bits,
+        };
+        assert!(asert.calculate_next_bits(&parent).is_ok(), "Rejected the Bitcoin Cash anchor");
+        for bits in [0x2100ffff, 0x1d01ffff] {
+            asert.anchor.bits = bits;
+            assert!(asert.calculate_next_bits(&parent).is_err(), "Accepted an anchor target of {:08x}", bits);
+        }
+        asert.pow_limit = bc_hash::digest::Digest([0xff; 32]);
+        asert.anchor.bits = 0x2100ffff;
+        assert!(asert.calculate_next_bits(&parent).is_err(), "Accepted an anchor target that would overflow");
+
+        for (target_spacing, half_life) in [(0, 172800), (600, 0), (-600, 172800)] {
+            let asert: Asert = Asert {
+                target_spacing,
+                half_life,
+                ..Asert::bitcoin_cash()
+            };
+            assert!(asert.next_bits(&chain[2015..], 0).is_err(), "Accepted a spacing of {} and half life of {}", target_spacing, half_life);
+        }
+
+        for (window, target_spacing) in [(0, 120), (45, 0), (u64::MAX, 120), (45, i64::MAX)] {
+            let lwma: Lwma = Lwma {
+                pow_limit: BitcoinRetarget::mainnet().pow_limit,
+                window,
+                target_spacing,
+            };
+            assert!(lwma.next_bits(&chain, 0).is_err(), "Accepted a window of {} and spacing of {}", window, target_spacing);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn extreme_timestamps() -> Result<(), Box<dyn Error>> {
+        // timespans that overflow are clamped like any other
+        let mainnet: BitcoinRetarget = BitcoinRetarget::mainnet();
+        let last: Header = Header {
+            height: 2015,
+            time: i64::MAX,
+            bits: 0x1c0ffff0,
+        };
+        let slowest: u32 = mainnet.calculate_next_bits(&last, 0)?;
+        assert!(mainnet.calculate_next_bits(&last, i64::MIN)? == slowest, "An overflowing timespan was not clamped");
+
+        // the 20 minute rule cannot overflow
+        let testnet: BitcoinRetarget = BitcoinRetarget::testnet();
+        let chain: Vec<Header> = vec![Header { height: 10, ..last }];
+        assert!(testnet.next_bits(&chain, i64::MAX).is_err(), "The 20 minute rule overflowed");
+
+        // neither can the ASERT exponent
+        let asert: Asert = Asert::bitcoin_cash();
+        let parent: Header = Header {
+            height: asert.anchor.height,
+            time: i64::MIN,
+            bits: asert.anchor.bits,
+        };
+        assert!(asert.calculate_next_bits(&parent).is_err(), "The ASERT time difference overflowed");
+        let parent: Header = Header { height: u64::MAX, ..parent };
+        assert!(asert.calculate_next_bits(&parent).is_err(), "The ASERT height difference overflowed");
+
+        // or the LWMA solve times
+        let lwma: Lwma = Lwma {
+            pow_limit: BitcoinRetarget::mainnet().pow_limit,
+            window: 2,
+            target_spacing: 120,
+        };
+        let mut chain: Vec<Header> = headers(0, 3, i64::MAX, 0, 0x1c010000);
+        assert!(lwma.next_bits(&chain, 0).is_err(), "Timestamps after i64::MAX were accepted");
+        chain[0].time = i64::MIN;
+        chain[1].time = i64::MAX - 1;
+        assert!(lwma.next_bits(&chain, 0).is_ok(), "An overflowing solve time was not capped");
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::dynamic::{new_hasher, Algorithm, DynHasher, DynWrapper};
+    use bc_hash::sha2::{Sha256, Sha512_256};
+    use bc_hash::sha3::Sha3_256;
+    use bc_hash::OneWayHasher;
+    use std::error::Error;
+    use std::str::FromStr;
+
+    #[test]
+    fn algorithm_names() -> Result<(), Box<dyn Error>> {
+        for algorithm in Algorithm::ALL {
+            assert!(
+                Algorithm::from_str(algorithm.name())? == *algorithm,
+                "Algorithm::from_str() failed for {}",
+                algorithm
+            );
+            let hasher: Box<dyn DynHasher> = algorithm.new_hasher();
+            assert!(hasher.name() == algorithm.name(), "The name of {} is wrong", algorithm);
+            assert!(hasher.block_size() == algorithm.block_size(), "The block size of {} is wrong", algorithm);
+            assert!(
+                algorithm.digest(b"abc").len() == algorithm.output_size(),
+                "The digest length of {} is wrong",
+                algorithm
+            );
+        }
+        assert!(Algorithm::from_str("SHA512_256")? == Algorithm::Sha512_256, "Lookup by an alternative spelling failed");
+        assert!(Algorithm::from_str("sha3-257").is_err(), "Found an algorithm that does not exist");
+        assert!(new_hasher("md5").is_err(), "Found an algorithm that does not exist");
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_digests() -> Result<(), Box<dyn Error>> {
+        // the algorithms a chain indexer might read from its configuration
+        for (name, expected) in [
+            ("sha256", Sha256::digest(b"hello world").to_vec()),
+            ("sha3-256", Sha3_256::digest(b"hello world").to_vec()),
+            ("sha512/256", Sha512_256::digest(b"hello world").to_vec()),
+        ] {
+            let mut hasher: Box<dyn DynHasher> = new_hasher(name)?;
+            hasher.update(b"hello ");
+            hasher.update(b"world");
+            assert!(hasher.finalize_into_vec() == expected, "The {} digest is wrong", name);
+            hasher.reset();
+            hasher.update(b"hello world");
+            assert!(hasher.finalize_into_vec() == expected, "Resetting {} failed", name);
+        }
+
+        let mut wrapped: DynWrapper<Sha256, 32> = DynWrapper::from(Sha256::init());
+        wrapped.update(b"hello world");
+        assert!(wrapped.output_size() == 32 && wrapped.name() == "sha256", "DynWrapper failed");
+        assert!(wrapped.finalize_into_vec() == Sha256::digest(b"hello world").to_vec(), "DynWrapper failed");
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::encoding::{self, Base64, Bech32Variant};
+    use bc_hash::ripemd::Hash160;
+    use bc_hash::sha2::Sha256;
+    use bc_hash::OneWayHasher;
+    use std::error::Error;
+    use std::str::FromStr;
+
+    #[test]
+    fn base58_vectors() -> Result<(), Box<dyn Error>> {
+        // leading zero bytes become leading ones
+        assert!(encoding::base58_encode(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]) == "11233QC4", "Base58 encoding failed");
+        assert!(encoding::base58_decode("11233QC4")? == [0, 0, 0x28, 0x7f, 0xb4, 0xcd], "Base58 decoding failed");
+        assert!(encoding::base58_encode(&[]).is_empty(), "Base58 encoding of nothing failed");
+        assert!(encoding::base58_decode("0OIl").is_err(), "Decoded characters that are not in the Base58 alphabet");
+
+        let digest: Digest<32> = Sha256::digest(b"hello world");
+        assert!(digest.to_base58() == "DULfJyE3WQqNxy3ymuhAChyNR3yufT88pmqvAazKFMG4", "Digest::to_base58() failed");
+        assert!(Digest::<32>::from_base58(&digest.to_base58())? == digest, "Digest::from_base58() failed");
+        assert!(Digest::<20>::from_base58(&digest.to_base58()).is_err(), "Decoded a digest of the wrong length");
+        Ok(())
+    }
+
+    #[test]
+    fn base58check_addresses() -> Result<(), Box<dyn Error>> {
+        // a P2PKH address is the Hash160 of a public key behind the version byte 0x00
+        let address: &str = "1BoatSLRHtKNngkdXEeobR76b53LETtpyT";
+        let hash: Digest<20> = Digest::from_str("7680adec8eabcabac676be9e83854ade0bd22cdb")?;
+        assert!(hash.to_base58check(&[0x00]) == address, "Digest::to_base58check() failed");
+        assert!(Digest::<20>::from_base58check(address, &[0x00])? == hash, "Digest::from_base58check() failed");
+        assert!(Digest::<20>::from_base58check(address, &[0x05]).is_err(), "Decoded an address with the wrong prefix");
+
+        // changing any character breaks the checksum
+        let typo: String = address.replace('H', "J");
+        assert!(encoding::base58check_decode(&typo).is_err(), "Decoded an address with a bad checksum");
+        assert!(encoding::base58check_decode("1111").is_err(), "Decoded a string without a checksum");
+
+        let payload: Digest<20> = Hash160::digest(b"abc");
+        let encoded: String = encoding::base58check_encode(&payload.0);
+        assert!(encoding::base58check_decode(&encoded)? == payload.0, "Base58Check round trip failed");
+        Ok(())
+    }
+
+    #[test]
+    fn base64_vectors() -> Result<(), Box<dyn Error>> {
+        // RFC 4648 section 10
+        let vectors: [(&str, &str); 7] = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (data, expected) in vectors {
+            assert!(encoding::base64_encode(data.as_bytes(), Base64::Standard) == expected, "Base64 of {} failed", data);
+            assert!(encoding::base64_decode(expected, Base64::Standard)? == data.as_bytes(), "Decoding {} failed", expected);
+            let unpadded: &str = expected.trim_end_matches('=');
+            assert!(encoding::base64_encode(data.as_bytes(), Base64::UrlSafe) == unpadded, "Base64url of {} failed", data);
+            assert!(encoding::base64_decode(unpadded, Base64::UrlSafe)? == data.as_bytes(), "Decoding {} failed", unpadded);
+        }
+
+        let digest: Digest<32> = Sha256::digest(b"hello world");
+        assert!(digest.to_base64(Base64::Standard) == "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=", "Digest::to_base64() failed");
+        assert!(digest.to_base64(Base64::UrlSafe) == "uU0nuZNNPgilLlLX2n2r-sSE7-N6U4DukIj3rOLvzek", "Digest::to_base64() failed");
+        for alphabet in [Base64::Standard, Base64::UrlSafe] {
+            assert!(Digest::<32>::from_base64(&digest.to_base64(alphabet), alphabet)? == digest, "Digest::from_base64() failed");
+        }
+
+        // bad alphabets, padding, lengths and trailing bits
+        for bad in ["uU0+", "Zg=", "Zg===", "Zm9v=", "Z", "Zh==", "Zm9=", "Zg==Zg=="] {
+            assert!(encoding::base64_decode(bad, Base64::UrlSafe).is_err(), "Decoded {}", bad);
+        }
+        assert!(encoding::base64_decode("uU0-", Base64::Standard).is_err(), "Decoded a url safe character");
+        Ok(())
+    }
+
+    #[test]
+    fn bech32_vectors() -> Result<(), Box<dyn Error>> {
+        // BIP173 and BIP350 test vectors
+        for valid in ["A12UEL5L", "a12uel5l", "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", "?1ezyfcl"] {
+            let (_, _, variant) = encoding::bech32_decode(valid)?;
+            assert!(variant == Bech32Variant::Bech32, "Decoding {} failed", valid);
+        }
+        for valid in ["A1LQFN3A", "a1lqfn3a", "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", "?1v759aa"] {
+            let (_, _, variant) = encoding::bech32_decode(valid)?;
+            assert!(variant == Bech32Variant::Bech32m, "Decoding {} failed", valid);
+        }
+        let (hrp, data, _) = encoding::bech32_decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw")?;
+        assert!(hrp == "abcdef" && data == (0..32).collect::<Vec<u8>>(), "Decoding the data failed");
+        for invalid in ["pzry9x0s0muk", "1pzry9x0s0muk", "x1b4n0q5v", "li1dgmt3", "A1G7SGD8", "A12uEL5L", "10a06t8"] {
+            assert!(encoding::bech32_decode(invalid).is_err(), "Decoded {}", invalid);
+        }
+
+        // segwit addresses put the witness version in front of the program
+        let v0: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let program: Digest<20> = Digest::from_str("751e76e8199196d454941c45d1b3a323f1433bd6")?;
+        let mut data: Vec<u8> = vec![0];
+        data.extend(encoding::convert_bits(&program.0, 8, 5, true)?);
+        assert!(encoding::bech32_encode("bc", &data, Bech32Variant::Bech32)? == v0, "Encoding a v0 address failed");
+        assert!(encoding::bech32_encode("BC", &data, Bech32Variant::Bech32)? == v0, "The hrp was not lowercased");
+
+        let v1: &str = "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0";
+        let (hrp, data, variant) = encoding::bech32_decode(v1)?;
+        let program: Vec<u8> = encoding::convert_bits(&data[1..], 5, 8, false)?;
+        assert!(hrp == "bc" && data[0] == 1 && variant == Bech32Variant::Bech32m, "Decoding a v1 address failed");
+        assert!(
+            program == Digest::<32>::from_str("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")?.0,
+            "Decoding a v1 program failed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bech32_digests() -> Result<(), Box<dyn Error>> {
+        let hash: Digest<20> = Digest::from_str("751e76e8199196d454941c45d1b3a323f1433bd6")?;
+        let encoded: String = hash.to_bech32("bc", Bech32Variant::Bech32)?;
+        assert!(encoded.starts_with("bc1w508d6qejxtdg4y5r3zarvary0c5xw7k"), "Digest::to_bech32() failed");
+
+        for variant in [Bech32Variant::Bech32, Bech32Variant::Bech32m] {
+            let digest: Digest<64> = bc_hash::sha2::Sha512::digest(b"abc");
+            let (hrp, decoded) = Digest::<64>::from_bech32(&digest.to_bech32("hash", variant)?, variant)?;
+            assert!(hrp == "hash" && decoded == digest, "Bech32 round trip failed");
+        }
+        assert!(Digest::<20>::from_bech32(&encoded, Bech32Variant::Bech32m).is_err(), "Decoded the wrong variant");
+        assert!(Digest::<32>::from_bech32(&encoded, Bech32Variant::Bech32).is_err(), "Decoded a digest of the wrong length");
+        assert!(hash.to_bech32("", Bech32Variant::Bech32).is_err(), "Encoded an empty hrp");
+        Ok(())
+    }
+}
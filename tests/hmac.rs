@@ -0,0 +1,153 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::hmac::{Hmac, HmacSha224, HmacSha256, HmacSha384, HmacSha3_256, HmacSha3_512, HmacSha512, HmacSha512_256};
+    use bc_hash::sha3::Keccak256;
+    use bc_hash::OneWayHasher;
+    use std::{error::Error, str::FromStr};
+
+    /// The keys and data of RFC 4231 test cases 1, 2, 3, 4, 6 and 7
+    fn rfc4231_cases() -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![
+            (vec![0x0b; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; 20], vec![0xdd; 50]),
+            ((1..26).collect(), vec![0xcd; 50]),
+            (vec![0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec()),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than block-size data. \
+                  The key needs to be hashed before being used by the HMAC algorithm."
+                    .to_vec(),
+            ),
+        ]
+    }
+
+    macro_rules! check_hmac {
+        ($hmac_type:ty, $mdlen:literal, $expected:expr, $msg:literal) => {
+            let mut digest: Digest<$mdlen> = Digest::new();
+            for ((key, data), expected) in rfc4231_cases().iter().zip($expected) {
+                let mut ctx = <$hmac_type>::new(key);
+                ctx.update(data).finish_into(&mut digest.0);
+                assert!(digest == Digest::from_str(expected)?, "{} {}", $msg, expected);
+                // reset must keep the key
+                ctx.reset().update(data).finish_into(&mut digest.0);
+                assert!(digest == Digest::from_str(expected)?, "Reset failed for {}", $msg);
+                assert!(ctx.reset().update(data).verify(&digest.0), "Verify failed for {}", $msg);
+            }
+        };
+    }
+
+    #[test]
+    fn hmac_rfc4231() -> Result<(), Box<dyn Error>> {
+        check_hmac!(
+            HmacSha224,
+            28,
+            [
+                "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+                "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+                "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+                "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+                "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+                "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+            ],
+            "HmacSha224 failed"
+        );
+        check_hmac!(
+            HmacSha256,
+            32,
+            [
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ],
+            "HmacSha256 failed"
+        );
+        check_hmac!(
+            HmacSha384,
+            48,
+            [
+                "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6",
+                "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649",
+                "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27",
+                "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e6801dd23c4a7d679ccf8a386c674cffb",
+                "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952",
+                "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e",
+            ],
+            "HmacSha384 failed"
+        );
+        check_hmac!(
+            HmacSha512,
+            64,
+            [
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+                "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3dba91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+                "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+            ],
+            "HmacSha512 failed"
+        );
+        check_hmac!(
+            HmacSha3_256,
+            32,
+            [
+                "ba85192310dffa96e2a3a40e69774351140bb7185e1202cdcc917589f95e16bb",
+                "c7d4072e788877ae3596bbb0da73b887c9171f93095b294ae857fbe2645e1ba5",
+                "84ec79124a27107865cedd8bd82da9965e5ed8c37b0ac98005a7f39ed58a4207",
+                "57366a45e2305321a4bc5aa5fe2ef8a921f6af8273d7fe7be6cfedb3f0aea6d7",
+                "ed73a374b96c005235f948032f09674a58c0ce555cfc1f223b02356560312c3b",
+                "65c5b06d4c3de32a7aef8763261e49adb6e2293ec8e7c61e8de61701fc63e123",
+            ],
+            "HmacSha3_256 failed"
+        );
+
+        // test case 5 truncates the output to 128 bits
+        let mut digest: Digest<32> = Digest::new();
+        HmacSha256::new(&[0x0c; 20]).update(b"Test With Truncation").finish_into(&mut digest.0);
+        assert!(
+            Digest::<16>::try_from(&digest.0[..16])? == Digest::from_str("a3b6167473100ee06e0c796c2955552b")?,
+            "Truncated HmacSha256 failed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn hmac_verify() -> Result<(), Box<dyn Error>> {
+        let mut tag: Digest<32> = Digest::new();
+        let mut ctx = HmacSha256::new(b"key");
+        ctx.update(b"The quick brown fox jumps over the lazy dog").finish_into(&mut tag.0);
+        assert!(
+            tag == Digest::from_str("f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8")?,
+            "HmacSha256 failed"
+        );
+
+        ctx.reset().update(b"The quick brown fox jumps over the lazy dog");
+        assert!(ctx.verify(&tag.0), "Verify rejected a valid tag");
+
+        tag[31] ^= 1;
+        ctx.reset().update(b"The quick brown fox jumps over the lazy dog");
+        assert!(!ctx.verify(&tag.0), "Verify accepted an invalid tag");
+
+        ctx.reset().update(b"The quick brown fox jumps over the lazy dog");
+        assert!(!ctx.verify(&tag.0[..16]), "Verify accepted a truncated tag");
+
+        Ok(())
+    }
+
+    #[test]
+    fn hmac_names() -> Result<(), Box<dyn Error>> {
+        assert!(HmacSha256::NAME == "hmac-sha256", "HmacSha256 has the wrong name");
+        assert!(HmacSha512_256::NAME == "hmac-sha512/256", "HmacSha512_256 has the wrong name");
+        assert!(HmacSha3_512::NAME == "hmac-sha3-512", "HmacSha3_512 has the wrong name");
+        // hashers without an alias fall back to the generic name
+        assert!(<Hmac<Keccak256, 32, 136>>::NAME == "hmac", "Hmac<Keccak256> has the wrong name");
+        Ok(())
+    }
+}
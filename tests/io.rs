@@ -47,7 +47,7 @@ pub mod test {
                     if e.kind() == ErrorKind::UnexpectedEof {
                         break;
                     } else {
-                        return Err(e)?;
+                        return Err(e.into());
                     }
                 }
             }
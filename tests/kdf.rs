@@ -0,0 +1,142 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::kdf::{pbkdf2, HkdfSha256, HkdfSha512};
+    use bc_hash::sha2::{Sha256, Sha512};
+    use std::{error::Error, str::FromStr};
+
+    #[test]
+    fn pbkdf2_vectors() -> Result<(), Box<dyn Error>> {
+        let mut key: Digest<32> = Digest::new();
+        pbkdf2::<Sha256, 32, 64>(b"password", b"salt", 1, &mut key.0)?;
+        assert!(
+            key == Digest::from_str("120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b")?,
+            "PBKDF2-HMAC-SHA256 with 1 iteration failed"
+        );
+        pbkdf2::<Sha256, 32, 64>(b"password", b"salt", 4096, &mut key.0)?;
+        assert!(
+            key == Digest::from_str("c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a")?,
+            "PBKDF2-HMAC-SHA256 with 4096 iterations failed"
+        );
+
+        // an output that spans more than one block
+        let mut key: Digest<40> = Digest::new();
+        pbkdf2::<Sha256, 32, 64>(
+            b"passwordPASSWORDpassword",
+            b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+            4096,
+            &mut key.0,
+        )?;
+        assert!(
+            key == Digest::from_str("348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9")?,
+            "PBKDF2-HMAC-SHA256 with a 40 byte output failed"
+        );
+
+        let mut key: Digest<16> = Digest::new();
+        pbkdf2::<Sha256, 32, 64>(b"pass\0word", b"sa\0lt", 4096, &mut key.0)?;
+        assert!(
+            key == Digest::from_str("89b69d0516f829893c696226650a8687")?,
+            "PBKDF2-HMAC-SHA256 with embedded nulls failed"
+        );
+
+        assert!(
+            pbkdf2::<Sha256, 32, 64>(b"password", b"salt", 0, &mut key.0).is_err(),
+            "PBKDF2 accepted zero iterations"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn bip39_seed() -> Result<(), Box<dyn Error>> {
+        let vectors: [(&str, &str); 2] = [
+            (
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+        ];
+        let mut seed: Digest<64> = Digest::new();
+        for (mnemonic, expected) in vectors {
+            pbkdf2::<Sha512, 64, 128>(mnemonic.as_bytes(), b"mnemonicTREZOR", 2048, &mut seed.0)?;
+            assert!(seed == Digest::from_str(expected)?, "BIP39 seed failed for {}", mnemonic);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hkdf_rfc5869() -> Result<(), Box<dyn Error>> {
+        // test case 1
+        let salt: Vec<u8> = (0x00..0x0d).collect();
+        let info: Vec<u8> = (0xf0..0xfa).collect();
+        let hkdf: HkdfSha256 = HkdfSha256::extract(&salt, &[0x0b; 22]);
+        assert!(
+            *hkdf.prk() == Digest::from_str("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")?.0,
+            "HKDF-SHA256 extract failed for test case 1"
+        );
+        let mut okm: Digest<42> = Digest::new();
+        hkdf.expand(&info, &mut okm.0)?;
+        assert!(
+            okm == Digest::from_str(
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+            )?,
+            "HKDF-SHA256 expand failed for test case 1"
+        );
+
+        // test case 2
+        let ikm: Vec<u8> = (0x00..0x50).collect();
+        let salt: Vec<u8> = (0x60..0xb0).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+        let hkdf: HkdfSha256 = HkdfSha256::extract(&salt, &ikm);
+        assert!(
+            *hkdf.prk() == Digest::from_str("06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244")?.0,
+            "HKDF-SHA256 extract failed for test case 2"
+        );
+        let mut okm: Digest<82> = Digest::new();
+        hkdf.expand(&info, &mut okm.0)?;
+        assert!(
+            okm == Digest::from_str(
+                "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c\
+                 59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71\
+                 cc30c58179ec3e87c14c01d5c1f3434f1d87"
+            )?,
+            "HKDF-SHA256 expand failed for test case 2"
+        );
+
+        // test case 3
+        let hkdf: HkdfSha256 = HkdfSha256::extract(&[], &[0x0b; 22]);
+        assert!(
+            *hkdf.prk() == Digest::from_str("19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04")?.0,
+            "HKDF-SHA256 extract failed for test case 3"
+        );
+        let mut okm: Digest<42> = Digest::new();
+        hkdf.expand(&[], &mut okm.0)?;
+        assert!(
+            okm == Digest::from_str(
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+            )?,
+            "HKDF-SHA256 expand failed for test case 3"
+        );
+
+        let mut okm: Vec<u8> = vec![0; 255 * 32 + 1];
+        assert!(hkdf.expand(&[], &mut okm).is_err(), "HKDF accepted an output that is too long");
+
+        let mut okm: Digest<100> = Digest::new();
+        HkdfSha512::extract(b"salt", b"secret").expand(b"info", &mut okm.0)?;
+        assert!(
+            okm == Digest::from_str(
+                "90e269f053d383c4b2070be93238adf358f3d67bd7b17ca3de95f10a50a8385e\
+                 66d8241c8c22c91cee75d7bda92e8111236b71b63c8f05973947428074d272f0\
+                 73de7d106ca1b6f5beeb22fc785a81666a9f00c675fdb5616858d14f472907a5\
+                 7fe282bb"
+            )?,
+            "HKDF-SHA512 failed"
+        );
+
+        Ok(())
+    }
+}
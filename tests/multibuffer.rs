@@ -0,0 +1,120 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::merkle::compute_root;
+    use bc_hash::multibuffer::{sha256_many, sha256_many_lanes, sha256d_many};
+    use bc_hash::sha2::{Sha256, Sha256d};
+    use sha2::Digest;
+    use std::error::Error;
+
+    fn messages<const LEN: usize>(count: usize) -> Vec<[u8; LEN]> {
+        (0..count)
+            .map(|i| {
+                let mut msg: [u8; LEN] = [0; LEN];
+                for (j, b) in msg.iter_mut().enumerate() {
+                    *b = (i * 131 + j * 17) as u8;
+                }
+                msg
+            })
+            .collect()
+    }
+
+    macro_rules! cmp_many {
+        ($len:literal) => {
+            // cover whole batches of lanes as well as the leftovers
+            for count in [0, 1, 3, 4, 7, 8, 9, 17] {
+                let msgs: Vec<[u8; $len]> = messages(count);
+                let mut digests: Vec<[u8; 32]> = vec![[0; 32]; count];
+                sha256_many(&msgs, &mut digests)?;
+                for (m, d) in msgs.iter().zip(digests.iter()) {
+                    assert!(d[..] == sha2::Sha256::digest(m)[..], "sha256_many failed for length {}", $len);
+                }
+                sha256d_many(&msgs, &mut digests)?;
+                for (m, d) in msgs.iter().zip(digests.iter()) {
+                    let first = sha2::Sha256::digest(m);
+                    assert!(d[..] == sha2::Sha256::digest(first)[..], "sha256d_many failed for length {}", $len);
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn many_messages() -> Result<(), Box<dyn Error>> {
+        // lengths around the padding boundaries
+        cmp_many!(0);
+        cmp_many!(32);
+        cmp_many!(55);
+        cmp_many!(56);
+        cmp_many!(64);
+        cmp_many!(119);
+        cmp_many!(200);
+
+        let mut digests: Vec<[u8; 32]> = vec![[0; 32]; 2];
+        assert!(sha256_many(&messages::<64>(3), &mut digests).is_err(), "sha256_many accepted mismatched lengths");
+
+        Ok(())
+    }
+
+    #[test]
+    fn every_lane_count() -> Result<(), Box<dyn Error>> {
+        // lanes() picks a single path, so force each of the others that this CPU can run
+        #[cfg(target_arch = "x86_64")]
+        let paths: [(usize, bool); 3] = [(1, true), (4, is_x86_feature_detected!("sse4.1")), (8, is_x86_feature_detected!("avx2"))];
+        #[cfg(not(target_arch = "x86_64"))]
+        let paths: [(usize, bool); 3] = [(1, true), (4, false), (8, false)];
+
+        let msgs: Vec<[u8; 119]> = messages(17);
+        let mut expected: Vec<[u8; 32]> = vec![[0; 32]; msgs.len()];
+        for (m, d) in msgs.iter().zip(expected.iter_mut()) {
+            d.copy_from_slice(&sha2::Sha256::digest(m));
+        }
+        for (lanes, supported) in paths {
+            let mut digests: Vec<[u8; 32]> = vec![[0; 32]; msgs.len()];
+            let result = sha256_many_lanes(lanes, &msgs, &mut digests);
+            assert!(result.is_ok() == supported, "sha256_many_lanes({}) disagreed with the CPU features", lanes);
+            if supported {
+                for (i, (d, e)) in digests.iter().zip(expected.iter()).enumerate() {
+                    assert!(d == e, "sha256_many_lanes({}) failed for lane {}", lanes, i);
+                }
+            }
+        }
+        assert!(sha256_many_lanes(2, &msgs, &mut expected).is_err(), "sha256_many_lanes accepted 2 lanes");
+
+        Ok(())
+    }
+
+    #[test]
+    fn merkle_levels() -> Result<(), Box<dyn Error>> {
+        for count in [1, 2, 5, 16, 33, 100] {
+            let leaves: Vec<[u8; 32]> = messages(count);
+
+            // calculate the expected roots one pair at a time
+            let mut single: Vec<[u8; 32]> = leaves.clone();
+            let mut double: Vec<[u8; 32]> = leaves.clone();
+            while single.len() > 1 {
+                if single.len() & 1 != 0 {
+                    single.push(*single.last().unwrap());
+                    double.push(*double.last().unwrap());
+                }
+                for i in 0..(single.len() / 2) {
+                    let a = sha2::Sha256::new().chain_update(single[i * 2]).chain_update(single[i * 2 + 1]).finalize();
+                    single[i].copy_from_slice(&a);
+                    let b = sha2::Sha256::new().chain_update(double[i * 2]).chain_update(double[i * 2 + 1]).finalize();
+                    double[i].copy_from_slice(&sha2::Sha256::digest(b));
+                }
+                single.truncate(single.len() / 2);
+                double.truncate(double.len() / 2);
+            }
+
+            let mut a: Vec<[u8; 32]> = leaves.clone();
+            compute_root::<32, Sha256>(&mut a)?;
+            assert!(a == single, "Sha256 merkle root of {} leaves failed", count);
+
+            let mut b: Vec<[u8; 32]> = leaves.clone();
+            compute_root::<32, Sha256d>(&mut b)?;
+            assert!(b == double, "Sha256d merkle root of {} leaves failed", count);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::dynamic::Algorithm;
+    use bc_hash::multihash;
+    use bc_hash::sha2::Sha256;
+    use bc_hash::OneWayHasher;
+    use std::error::Error;
+    use std::str::FromStr;
+
+    #[test]
+    fn multihash_vectors() -> Result<(), Box<dyn Error>> {
+        // the sha2-256 multihash of "hello world"
+        let expected: Digest<34> =
+            Digest::from_str("1220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")?;
+        let digest: Digest<32> = Sha256::digest(b"hello world");
+        assert!(digest.to_multihash(Algorithm::Sha256)? == expected.0, "Digest::to_multihash() failed");
+        assert!(multihash::digest(0x12, b"hello world")? == expected.0, "multihash::digest() failed");
+
+        let (algorithm, decoded) = Digest::<32>::from_multihash(&expected.0)?;
+        assert!(algorithm == Algorithm::Sha256 && decoded == digest, "Digest::from_multihash() failed");
+
+        // sha2-512/256 has a two byte code
+        let encoded: Vec<u8> = multihash::digest(0x1015, b"abc")?;
+        assert!(encoded[..3] == [0x95, 0x20, 0x20], "Encoding a two byte code failed");
+        Ok(())
+    }
+
+    #[test]
+    fn multihash_round_trips() -> Result<(), Box<dyn Error>> {
+        for algorithm in Algorithm::ALL {
+            let code: u64 = match algorithm.multihash_code() {
+                Some(code) => code,
+                None => continue,
+            };
+            assert!(Algorithm::from_multihash_code(code)? == *algorithm, "Looking up the code of {} failed", algorithm);
+            let encoded: Vec<u8> = multihash::digest(code, b"abc")?;
+            let (decoded, digest) = multihash::decode(&encoded)?;
+            assert!(decoded == *algorithm && digest == algorithm.digest(b"abc"), "Round trip failed for {}", algorithm);
+        }
+        assert!(Algorithm::Hash160.multihash_code().is_none(), "Hash160 has no multihash code");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_multihashes() -> Result<(), Box<dyn Error>> {
+        let valid: Vec<u8> = multihash::digest(0x12, b"abc")?;
+
+        // truncated and extended digests
+        assert!(multihash::decode(&valid[..33]).is_err(), "Decoded a truncated multihash");
+        let mut longer: Vec<u8> = valid.clone();
+        longer.push(0);
+        assert!(multihash::decode(&longer).is_err(), "Decoded a multihash with extra bytes");
+
+        // a declared length that does not match the code
+        let mut wrong_len: Vec<u8> = valid.clone();
+        wrong_len[1] = 0x1f;
+        wrong_len.pop();
+        assert!(multihash::decode(&wrong_len).is_err(), "Decoded a multihash with the wrong length for its code");
+
+        // unknown codes and non-minimal varints
+        assert!(multihash::digest(0x00, b"abc").is_err(), "Hashed with an unknown code");
+        let mut overlong: Vec<u8> = vec![0x92, 0x00];
+        overlong.extend_from_slice(&valid[1..]);
+        assert!(multihash::decode(&overlong).is_err(), "Decoded a non-minimal varint");
+        assert!(multihash::decode(&[0x80; 10]).is_err(), "Decoded a varint that is too long");
+
+        // digests of the wrong size
+        assert!(Digest::<64>::from_multihash(&valid).is_err(), "Decoded a multihash into a digest of the wrong size");
+        assert!(Digest::<20>::new().to_multihash(Algorithm::Sha256).is_err(), "Encoded a digest of the wrong size");
+        Ok(())
+    }
+}
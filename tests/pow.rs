@@ -0,0 +1,77 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use std::cmp::Ordering;
+    use std::error::Error;
+
+    /// Returns a target as a little-endian digest from its usual big-endian hexidecimal form.
+    fn target(hex: &str) -> Result<Digest<32>, Box<dyn Error>> {
+        Ok(Digest::from_rev_str(&format!("{:0>64}", hex))?)
+    }
+
+    #[test]
+    fn digest_ordering() -> Result<(), Box<dyn Error>> {
+        let mut a: Digest<32> = Digest::new();
+        let mut b: Digest<32> = Digest::new();
+        a[0] = 2;
+        b[31] = 1;
+        assert!(a.cmp_le(&b) == Ordering::Less && b.cmp_le(&a) == Ordering::Greater, "Digest::cmp_le() failed");
+        assert!(a.cmp_be(&b) == Ordering::Greater && b.cmp_be(&a) == Ordering::Less, "Digest::cmp_be() failed");
+        assert!(a.cmp_le(&a) == Ordering::Equal && a.cmp_be(&a) == Ordering::Equal, "Comparing equal digests failed");
+
+        assert!(Digest::<32>::new().leading_zero_bits() == 256, "Leading zeros of zero failed");
+        assert!(b.leading_zero_bits() == 7, "Leading zeros of the high byte failed");
+        assert!(a.leading_zero_bits() == 254, "Leading zeros of the low byte failed");
+        assert!(Digest([0xff; 20]).leading_zero_bits() == 0, "Leading zeros of the maximum failed");
+        Ok(())
+    }
+
+    #[test]
+    fn compact_targets() -> Result<(), Box<dyn Error>> {
+        // vectors from Bitcoin Core's arith_uint256 tests, as (nBits, target, re-encoded nBits)
+        let vectors: [(u32, &str, u32); 9] = [
+            (0x00123456, "0", 0),
+            (0x01003456, "0", 0),
+            (0x01123456, "12", 0x01120000),
+            (0x02008000, "80", 0x02008000),
+            (0x03123456, "123456", 0x03123456),
+            (0x04123456, "12345600", 0x04123456),
+            (0x05009234, "92340000", 0x05009234),
+            (0x20123456, "1234560000000000000000000000000000000000000000000000000000000000", 0x20123456),
+            (0x1d00ffff, "ffff0000000000000000000000000000000000000000000000000000", 0x1d00ffff),
+        ];
+        for (bits, hex, compact) in vectors {
+            let t: Digest<32> = Digest::from_compact(bits)?;
+            assert!(t == target(hex)?, "Decoding {:08x} failed", bits);
+            assert!(t.to_compact() == compact, "Encoding {:08x} failed", bits);
+        }
+
+        // negative and overflowing targets
+        for bits in [0x04923456, 0x01fedcba, 0xff123456, 0x21010000, 0x22000100] {
+            assert!(Digest::<32>::from_compact(bits).is_err(), "Decoded {:08x}", bits);
+        }
+
+        // encoding rounds down to three significant bytes
+        assert!(target("123456789")?.to_compact() == 0x05012345, "Rounding a target failed");
+        Ok(())
+    }
+
+    #[test]
+    fn block_proof_of_work() -> Result<(), Box<dyn Error>> {
+        // the Bitcoin genesis block
+        let hash: Digest<32> = Digest::from_rev_str("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f")?;
+        let limit: Digest<32> = Digest::from_compact(0x1d00ffff)?;
+        assert!(hash.meets_target(&limit), "The genesis block does not meet its target");
+        assert!(!hash.meets_target(&Digest::from_compact(0x1b0404cb)?), "The genesis block meets a harder target");
+        assert!(hash.leading_zero_bits() == 43, "Leading zeros of the genesis block failed");
+        assert!(limit.meets_target(&limit), "A hash equal to the target must meet it");
+
+        // the genesis block adds 0x100010001 to the chainwork
+        assert!(limit.chainwork()? == target("100010001")?, "Chainwork of the genesis target failed");
+        assert!(target("1")?.chainwork()? == target("8000000000000000000000000000000000000000000000000000000000000000")?, "Chainwork of one failed");
+        assert!(Digest([0xff; 32]).chainwork()? == target("1")?, "Chainwork of the maximum target failed");
+        assert!(Digest::<32>::new().chainwork().is_err(), "Chainwork of a zero target");
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::ripemd::{Hash160, Ripemd160};
+    use bc_hash::OneWayHasher;
+    use std::{error::Error, io::Write, str::FromStr};
+
+    #[test]
+    fn ripemd160_vectors() -> Result<(), Box<dyn Error>> {
+        let vectors: [(&[u8], &str); 5] = [
+            (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (b"message digest", "5d0689ef49d2fae572b881b123a85ffa21595f36"),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            ),
+        ];
+        let mut digest: Digest<20> = Digest::new();
+        let mut ctx = Ripemd160::init();
+        for (data, expected) in vectors {
+            ctx.reset().update(data).finish_into(&mut digest.0);
+            assert!(digest == Digest::from_str(expected)?, "Ripemd160 failed for {:?}", data);
+        }
+
+        // one million repetitions of "a" written in uneven pieces
+        ctx.reset();
+        let data: Vec<u8> = vec![b'a'; 1_000_000];
+        for chunk in data.chunks(999) {
+            ctx.write_all(chunk)?;
+        }
+        ctx.finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("52783243c1697bdbe16d37f97f68f08325dc1528")?,
+            "Ripemd160 failed for one million a's"
+        );
+
+        assert!(
+            ctx.reset().finish() == Digest::from_str("9c1185a5c5e9fc54612808977ee8f548b2258d31")?,
+            "Ripemd160 of the empty string failed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash160_vectors() -> Result<(), Box<dyn Error>> {
+        let mut digest: Digest<20> = Digest::new();
+        let mut ctx = Hash160::init();
+
+        ctx.update(b"hello world").finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("d7d5ee7824ff93f94c3055af9382c86c68b5ca92")?,
+            "Hash160 of \"hello world\" failed"
+        );
+
+        // the compressed public key for the private key 1
+        let pubkey: Digest<33> =
+            Digest::from_str("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")?;
+        ctx.reset().update(&pubkey.0).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("751e76e8199196d454941c45d1b3a323f1433bd6")?,
+            "Hash160 of a public key failed"
+        );
+
+        Ok(())
+    }
+}
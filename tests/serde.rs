@@ -0,0 +1,62 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::merkle::{compute_proof, prove, ChildNode, Proof};
+    use bc_hash::sha2::Sha256;
+    use bc_hash::OneWayHasher;
+    use std::error::Error;
+
+    #[test]
+    fn digest_round_trips() -> Result<(), Box<dyn Error>> {
+        let digest: Digest<32> = Sha256::digest(b"hello world");
+
+        // JSON uses a hex string
+        let json: String = serde_json::to_string(&digest)?;
+        assert!(
+            json == "\"b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9\"",
+            "Serializing a digest to JSON failed"
+        );
+        assert!(serde_json::from_str::<Digest<32>>(&json)? == digest, "Deserializing a digest from JSON failed");
+        assert!(serde_json::from_str::<Digest<32>>("\"b94d\"").is_err(), "Deserialized a short hex string");
+        assert!(serde_json::from_str::<Digest<20>>(&json).is_err(), "Deserialized a digest of the wrong size");
+
+        // strings that are not plain hexidecimal are errors rather than panics
+        assert!(serde_json::from_str::<Digest<2>>("\"aé1\"").is_err(), "Deserialized a non-ASCII string");
+        assert!(serde_json::from_str::<Digest<2>>("\"+f+f\"").is_err(), "Deserialized a string with signs");
+        assert!(serde_json::from_str::<Digest<2>>("\"0f0f\"")? == [0x0f, 0x0f], "Deserializing a short digest failed");
+
+        // bincode uses the raw bytes behind a length prefix
+        let bytes: Vec<u8> = bincode::serialize(&digest)?;
+        assert!(bytes.len() == 8 + 32 && bytes[8..] == digest.0, "Serializing a digest to bincode failed");
+        assert!(bincode::deserialize::<Digest<32>>(&bytes)? == digest, "Deserializing a digest from bincode failed");
+        assert!(bincode::deserialize::<Digest<64>>(&bytes).is_err(), "Deserialized a digest of the wrong size");
+        Ok(())
+    }
+
+    #[test]
+    fn proof_round_trips() -> Result<(), Box<dyn Error>> {
+        let leaves: Vec<[u8; 32]> = (0..5u8).map(|i| Sha256::digest([i]).0).collect();
+        let (proof, _) = compute_proof::<32, Sha256>(&mut leaves.clone(), 2)?;
+
+        let json: String = serde_json::to_string(&proof)?;
+        assert!(json.starts_with("[{\"right\":\""), "Proof nodes are not tagged in JSON: {}", json);
+        let from_json: Proof<32> = serde_json::from_str(&json)?;
+        assert!(from_json == proof, "Deserializing a proof from JSON failed");
+
+        let from_bincode: Proof<32> = bincode::deserialize(&bincode::serialize(&proof)?)?;
+        assert!(from_bincode == proof, "Deserializing a proof from bincode failed");
+
+        // the deserialized proof still leads to the root
+        let mut root: Vec<[u8; 32]> = leaves.clone();
+        bc_hash::merkle::compute_root::<32, Sha256>(&mut root)?;
+        let mut digest: [u8; 32] = leaves[2];
+        prove::<32, Sha256>(from_json, &mut digest);
+        assert!(digest == root[0], "The deserialized proof does not lead to the root");
+
+        let node: ChildNode<4> = serde_json::from_str("{\"left\":\"deadbeef\"}")?;
+        assert!(node == ChildNode::Left([0xde, 0xad, 0xbe, 0xef]), "Deserializing a left node failed");
+        assert!(serde_json::from_str::<ChildNode<4>>("{\"up\":\"deadbeef\"}").is_err(), "Deserialized an unknown tag");
+        Ok(())
+    }
+}
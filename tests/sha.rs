@@ -15,7 +15,7 @@ pub mod test {
             let mut ctx = <$bc_type>::init();
             let a = {
                 ctx.update(&$data[..]);
-                ctx.finish(&mut digest.0);
+                ctx.finish_into(&mut digest.0);
                 digest.0
             };
             let b = {
@@ -29,7 +29,7 @@ pub mod test {
             let c = {
                 ctx.reset();
                 ctx.update(&$data[..]);
-                ctx.finish(&mut digest.0);
+                ctx.finish_into(&mut digest.0);
                 digest.0
             };
             assert!(a == c, "Reset failed for {:?}", digest);
@@ -42,7 +42,7 @@ pub mod test {
             let mut ctx = <$bc_type>::init();
             let a = {
                 ctx.update(&$data[..]);
-                ctx.finish(&mut digest.0);
+                ctx.finish_into(&mut digest.0);
                 digest.0
             };
             let b = {
@@ -57,7 +57,7 @@ pub mod test {
             let c = {
                 ctx.reset();
                 ctx.update(&$data[..]);
-                ctx.finish(&mut digest.0);
+                ctx.finish_into(&mut digest.0);
                 digest.0
             };
             assert!(a == c, "Reset failed for {:?}", digest);
@@ -146,6 +146,35 @@ pub mod test {
             "Sha3_512 failed"
         );
 
+        cmp_fixed_len_digests!(
+            bc_hash::sha3::Keccak224,
+            sha3::Keccak224,
+            28,
+            data,
+            "Keccak224 failed"
+        );
+        cmp_fixed_len_digests!(
+            bc_hash::sha3::Keccak256,
+            sha3::Keccak256,
+            32,
+            data,
+            "Keccak256 failed"
+        );
+        cmp_fixed_len_digests!(
+            bc_hash::sha3::Keccak384,
+            sha3::Keccak384,
+            48,
+            data,
+            "Keccak384 failed"
+        );
+        cmp_fixed_len_digests!(
+            bc_hash::sha3::Keccak512,
+            sha3::Keccak512,
+            64,
+            data,
+            "Keccak512 failed"
+        );
+
         // compare variable length digests
 
         cmp_variable_len_digests!(
@@ -165,4 +194,286 @@ pub mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn shake_xof_reader() -> Result<(), Box<dyn Error>> {
+        use bc_hash::FinishXOF;
+
+        let data: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+        // squeeze in uneven chunks that cross the rate boundary several times
+        let mut ctx = bc_hash::sha3::Shake128::<32>::init();
+        ctx.update(data);
+        let mut reader = ctx.finish_reader();
+        let mut a: Vec<u8> = vec![0; 1000];
+        let mut pos: usize = 0;
+        for len in [1, 7, 160, 168, 3, 500, 161] {
+            reader.read_exact(&mut a[pos..(pos + len)])?;
+            pos += len;
+        }
+
+        let mut expected = Shake128::default();
+        expected.update(data);
+        let mut b: Vec<u8> = vec![0; 1000];
+        XofReader::read(&mut expected.finalize_xof(), &mut b);
+        assert!(a == b, "Shake128 XofReader failed");
+
+        // the context is left untouched, so finishing it normally gives the same output
+        let mut c: Vec<u8> = vec![0; 1000];
+        ctx.finish_xof(&mut c);
+        assert!(a == c, "Shake128 finish_reader() modified the context");
+
+        let mut ctx = bc_hash::sha3::Shake256::<64>::init();
+        let mut reader = ctx.update(data).finish_reader();
+        let mut a: Vec<u8> = vec![0; 1000];
+        for chunk in a.chunks_mut(135) {
+            reader.squeeze(chunk);
+        }
+
+        let mut expected = Shake256::default();
+        expected.update(data);
+        let mut b: Vec<u8> = vec![0; 1000];
+        XofReader::read(&mut expected.finalize_xof(), &mut b);
+        assert!(a == b, "Shake256 XofReader failed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha256_compress_paths() -> Result<(), Box<dyn Error>> {
+        use bc_hash::sha2::{compress256, compress256_portable};
+
+        let chunks: Vec<[u8; 64]> = (0..100u32)
+            .map(|i| {
+                let mut chunk: [u8; 64] = [0; 64];
+                for (j, b) in chunk.iter_mut().enumerate() {
+                    *b = (i as usize * 31 + j * 7) as u8;
+                }
+                chunk
+            })
+            .collect();
+        let mut a: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+        let mut b: [u32; 8] = a;
+        compress256(&mut a, &chunks);
+        compress256_portable(&mut b, &chunks);
+        assert!(a == b, "compress256() and compress256_portable() disagree");
+
+        // feed the data in pieces that do not line up with the chunks
+        let data: Vec<u8> = chunks.concat();
+        let mut digest: bc_hash::digest::Digest<32> = bc_hash::digest::Digest::new();
+        let mut ctx = bc_hash::sha2::Sha256::init();
+        ctx.update(&data[..5]).update(&data[5..200]).update(&data[200..6000]).update(&data[6000..]);
+        ctx.finish_into(&mut digest.0);
+        assert!(digest.0[..] == sha2::Sha256::digest(&data)[..], "Sha256 with unaligned updates failed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha2_midstates() -> Result<(), Box<dyn Error>> {
+        use bc_hash::sha2::{Midstate256, Midstate512, Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+
+        let data: Vec<u8> = (0..300u32).map(|i| (i % 251) as u8).collect();
+
+        // absorb the first chunks, save the midstate as bytes, then resume and finish from it
+        macro_rules! resume {
+            ($bc_type:ty, $other_type:ty, $midstate:ty, $mdlen:literal, $chunk_len:literal) => {
+                let mut ctx = <$bc_type>::init();
+                ctx.update(&data[..($chunk_len * 2)]);
+                let bytes = ctx.midstate()?.to_bytes();
+                let mut resumed = <$bc_type>::from_midstate(&<$midstate>::from_bytes(&bytes)?)?;
+                let mut digest: [u8; $mdlen] = [0; $mdlen];
+                resumed.update(&data[($chunk_len * 2)..]).finish_into(&mut digest);
+                assert!(digest[..] == <$other_type>::digest(&data)[..], "Resuming {} from a midstate failed", stringify!($bc_type));
+
+                ctx.update(&data[..1]);
+                assert!(ctx.midstate().is_err(), "{} exported a midstate with a partial chunk", stringify!($bc_type));
+            };
+        }
+
+        resume!(Sha224, sha2::Sha224, Midstate256, 28, 64);
+        resume!(Sha256, sha2::Sha256, Midstate256, 32, 64);
+        resume!(Sha384, sha2::Sha384, Midstate512, 48, 128);
+        resume!(Sha512, sha2::Sha512, Midstate512, 64, 128);
+        resume!(Sha512_224, sha2::Sha512_224, Midstate512, 28, 128);
+        resume!(Sha512_256, sha2::Sha512_256, Midstate512, 32, 128);
+
+        // grind the nonce of the genesis block header from the midstate of its first 64 bytes
+        let header: bc_hash::digest::Digest<80> = std::str::FromStr::from_str(
+            "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b2\
+             7ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c",
+        )?;
+        let midstate: Midstate256 = Sha256::init().update(&header.0[..64]).midstate()?;
+        let mut ctx = Sha256::init();
+        let mut tail: [u8; 16] = header.0[64..].try_into()?;
+        let mut found: Option<u32> = None;
+        for nonce in 0x7c2bac1du32 - 10..=0x7c2bac1du32 + 10 {
+            let mut first: [u8; 32] = [0; 32];
+            let mut second: [u8; 32] = [0; 32];
+            tail[12..].copy_from_slice(&nonce.to_le_bytes());
+            ctx.set_midstate(&midstate)?.update(&tail).finish_into(&mut first);
+            ctx.reset().update(&first).finish_into(&mut second);
+            if second[28..] == [0; 4] {
+                found = Some(nonce);
+                break;
+            }
+        }
+        assert!(found == Some(0x7c2bac1d), "Grinding the genesis block nonce from a midstate failed");
+
+        let mut bad: Midstate256 = midstate;
+        bad.len += 1;
+        assert!(Sha256::from_midstate(&bad).is_err(), "Resumed a midstate with a partial chunk");
+
+        Ok(())
+    }
+
+    #[test]
+    fn generic_finish() -> Result<(), Box<dyn Error>> {
+        use bc_hash::digest::Digest;
+
+        // the same generic code works for both the SHA-2 and SHA-3 families
+        fn hash<H: OneWayHasher<32>>(data: &[u8]) -> (Digest<32>, [u8; 32]) {
+            let mut ctx: H = H::init();
+            let digest: Digest<32> = ctx.update(data).finish();
+            let mut bytes: [u8; 32] = [0; 32];
+            ctx.reset().update(data).finish_into(&mut bytes);
+            (digest, bytes)
+        }
+
+        let (a, b) = hash::<bc_hash::sha2::Sha256>(b"abc");
+        assert!(a == b && a.0[..] == sha2::Sha256::digest(b"abc")[..], "Sha256 finish() failed");
+        let (a, b) = hash::<bc_hash::sha3::Sha3_256>(b"abc");
+        assert!(a == b && a.0[..] == sha3::Sha3_256::digest(b"abc")[..], "Sha3_256 finish() failed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn one_shot_digests() -> Result<(), Box<dyn Error>> {
+        use bc_hash::digest::Digest;
+        use bc_hash::sha2::Sha256;
+        use bc_hash::sha3::Keccak256;
+        use std::path::PathBuf;
+        use std::str::FromStr;
+
+        /// Removes the file when the test ends, even if an assertion fails first.
+        struct TempFile(PathBuf);
+
+        impl Drop for TempFile {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_file(&self.0);
+            }
+        }
+
+        let expected: Digest<32> = Digest::from_str("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")?;
+        assert!(Sha256::digest("hello world") == expected, "Sha256::digest() of a &str failed");
+        assert!(Sha256::digest(String::from("hello world")) == expected, "Sha256::digest() of a String failed");
+        let bytes: Vec<u8> = Vec::from("hello world");
+        assert!(Sha256::digest(bytes) == expected, "Sha256::digest() of a Vec failed");
+        assert!(
+            Keccak256::digest(b"") == Digest::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")?,
+            "Keccak256::digest() failed"
+        );
+
+        // more data than fits in the buffer used to copy from the reader
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        assert!(Sha256::digest_reader(&data[..])? == Sha256::digest(&data), "Sha256::digest_reader() failed");
+
+        // a name unique to this process so that parallel runs do not collide
+        let file: TempFile = TempFile(std::env::temp_dir().join(format!("bc_hash.one_shot_digests.{}", std::process::id())));
+        std::fs::write(&file.0, &data)?;
+        let digest: Digest<32> = Sha256::digest_file(&file.0)?;
+        assert!(digest.0[..] == sha2::Sha256::digest(&data)[..], "Sha256::digest_file() failed");
+        std::fs::remove_file(&file.0)?;
+        assert!(Sha256::digest_file(&file.0).is_err(), "Sha256::digest_file() hashed a file that does not exist");
+        assert!(Sha256::digest_file(&std::env::temp_dir()).is_err(), "Sha256::digest_file() hashed a directory");
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha3_unaligned_updates() -> Result<(), Box<dyn Error>> {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let mut digest: bc_hash::digest::Digest<32> = bc_hash::digest::Digest::new();
+
+        // split the input so that it starts, ends and crosses the 136 byte rate mid-block
+        let mut ctx = bc_hash::sha3::Sha3_256::init();
+        for range in [0..1, 1..136, 136..137, 137..500, 500..772, 772..1000] {
+            ctx.update(&data[range]);
+        }
+        ctx.finish_into(&mut digest.0);
+        assert!(digest.0[..] == sha3::Sha3_256::digest(&data)[..], "Sha3_256 with unaligned updates failed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn keccak_ethereum_vectors() -> Result<(), Box<dyn Error>> {
+        use bc_hash::digest::Digest;
+        use bc_hash::sha3::Keccak256;
+        use std::str::FromStr;
+
+        let mut digest: Digest<32> = Digest::new();
+        let mut ctx = Keccak256::init();
+        ctx.finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")?,
+            "Keccak256 of the empty string failed"
+        );
+
+        ctx.reset().update(b"hello world").finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad")?,
+            "Keccak256 of \"hello world\" failed"
+        );
+
+        // the function selector for ERC-20 transfer(address,uint256)
+        ctx.reset().update(b"transfer(address,uint256)").finish_into(&mut digest.0);
+        assert!(digest.0[..4] == [0xa9, 0x05, 0x9c, 0xbb], "Keccak256 function selector failed");
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha256d_bitcoin_vectors() -> Result<(), Box<dyn Error>> {
+        use bc_hash::digest::Digest;
+        use bc_hash::merkle::compute_root;
+        use bc_hash::sha2::Sha256d;
+        use std::str::FromStr;
+
+        // the genesis block header
+        let header: Digest<80> = Digest::from_str(
+            "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b2\
+             7ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c",
+        )?;
+        let mut digest: Digest<32> = Digest::new();
+        let mut ctx = Sha256d::init();
+        ctx.update(&header.0).finish_into(&mut digest.0);
+        assert!(
+            digest.to_rev_string() == "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
+            "Sha256d of the genesis block header failed"
+        );
+        assert!(
+            digest == Digest::from_rev_str("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f")?,
+            "Digest::from_rev_str() failed"
+        );
+
+        // the transactions and merkle root of block 100000
+        let mut leaves: Vec<[u8; 32]> = Vec::new();
+        for txid in [
+            "8c14f0db3df150123e6f3dbbf30f8b955a8249b62ac1d1ff16284aefa3d06d87",
+            "fff2525b8931402dd09222c50775608f75787bd2b87e56995a7bdd30f79702c4",
+            "6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4",
+            "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d",
+        ] {
+            leaves.push(Digest::<32>::from_rev_str(txid)?.0);
+        }
+        compute_root::<32, Sha256d>(&mut leaves)?;
+        assert!(
+            Digest::from(leaves[0]).to_rev_string() == "f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766",
+            "Sha256d merkle root of block 100000 failed"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,228 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::digest::Digest;
+    use bc_hash::sp800_185::{
+        left_encode, right_encode, CShake128, CShake256, Kmac128, Kmac256, ParallelHash128, ParallelHash256,
+        TupleHash128, TupleHash256,
+    };
+    use bc_hash::{FinishXOF, OneWayHasher};
+    use std::{error::Error, str::FromStr};
+
+    #[test]
+    fn encodings() {
+        assert!(left_encode(0) == [1, 0], "left_encode(0) failed");
+        assert!(left_encode(168) == [1, 168], "left_encode(168) failed");
+        assert!(left_encode(256) == [2, 1, 0], "left_encode(256) failed");
+        assert!(right_encode(0) == [0, 1], "right_encode(0) failed");
+        assert!(right_encode(256) == [1, 0, 2], "right_encode(256) failed");
+    }
+
+    #[test]
+    fn cshake_vectors() -> Result<(), Box<dyn Error>> {
+        let short: Vec<u8> = (0..4).collect();
+        let long: Vec<u8> = (0..200).collect();
+
+        let mut digest: Digest<32> = Digest::new();
+        let mut ctx: CShake128<32> = CShake128::new(b"", b"Email Signature");
+        ctx.update(&short).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("c1c36925b6409a04f1b504fcbca9d82b4017277cb5ed2b2065fc1d3814d5aaf5")?,
+            "cSHAKE128 sample 1 failed"
+        );
+        ctx.reset().update(&long).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("c5221d50e4f822d96a2e8881a961420f294b7b24fe3d2094baed2c6524cc166b")?,
+            "cSHAKE128 sample 2 failed"
+        );
+
+        // with an empty function name and customization string cSHAKE is equal to SHAKE
+        CShake128::<32>::init().update(&short).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("0b0cc28e60e37698b411234b1158a5d42636440432a28e8b8df5be04208878f9")?,
+            "cSHAKE128 without customization failed"
+        );
+
+        let mut digest: Digest<64> = Digest::new();
+        CShake256::<64>::new(b"", b"Email Signature").update(&short).finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "d008828e2b80ac9d2218ffee1d070c48b8e4c87bff32c9699d5b6896eee0edd1\
+                     64020e2be0560858d9c00c037e34a96937c561a74c412bb4c746469527281c8c"
+                )?,
+            "cSHAKE256 sample 3 failed"
+        );
+        CShake256::<64>::new(b"MyFunc", b"Custom").update(&long).finish_xof(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "1d391caa02aff7077dabb51d9963228869091e5f7929b53ed113895eea396546\
+                     88594c38bc87c2fcca8648810b9b08b2506482561c724f4f1bd8e30fd79a7062"
+                )?,
+            "cSHAKE256 with a function name failed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn kmac_vectors() -> Result<(), Box<dyn Error>> {
+        let key: Vec<u8> = (0x40..0x60).collect();
+        let short: Vec<u8> = (0..4).collect();
+        let long: Vec<u8> = (0..200).collect();
+
+        let mut digest: Digest<32> = Digest::new();
+        Kmac128::<32>::new(&key, b"").update(&short).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e")?,
+            "KMAC128 sample 1 failed"
+        );
+
+        let mut ctx: Kmac128<32> = Kmac128::new(&key, b"My Tagged Application");
+        ctx.update(&long).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230")?,
+            "KMAC128 sample 3 failed"
+        );
+        ctx.reset().update(&long).finish_xof(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("47026c7cd793084aa0283c253ef658490c0db61438b8326fe9bddf281b83ae0f")?,
+            "KMACXOF128 failed"
+        );
+
+        // verify must accept the tag from sample 3 and nothing else
+        let mut tag: Digest<32> = Digest::from_str("1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230")?;
+        assert!(ctx.reset().update(&long).verify(&tag.0), "KMAC128 verify rejected a valid tag");
+        tag[31] ^= 1;
+        assert!(!ctx.reset().update(&long).verify(&tag.0), "KMAC128 verify accepted an invalid tag");
+        assert!(!ctx.reset().update(&long).verify(&tag.0[..16]), "KMAC128 verify accepted a truncated tag");
+
+        let mut digest: Digest<64> = Digest::new();
+        let mut ctx: Kmac256<64> = Kmac256::new(&key, b"My Tagged Application");
+        ctx.update(&short).finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7\
+                     f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd"
+                )?,
+            "KMAC256 sample 4 failed"
+        );
+        ctx.reset().update(&long).finish_xof(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "d5be731c954ed7732846bb59dbe3a8e30f83e77a4bff4459f2f1c2b4ecebb8ce\
+                     67ba01c62e8ab8578d2d499bd1bb276768781190020a306a97de281dcc30305d"
+                )?,
+            "KMACXOF256 failed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tuple_hash_vectors() -> Result<(), Box<dyn Error>> {
+        let t1: Vec<u8> = (0x00..0x03).collect();
+        let t2: Vec<u8> = (0x10..0x16).collect();
+        let t3: Vec<u8> = (0x20..0x29).collect();
+
+        let mut digest: Digest<32> = Digest::new();
+        TupleHash128::<32>::new(&[]).push_element(&t1).push_element(&t2).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("c5d8786c1afb9b82111ab34b65b2c0048fa64e6d48e263264ce1707d3ffc8ed1")?,
+            "TupleHash128 sample 1 failed"
+        );
+
+        let mut ctx: TupleHash128<32> = TupleHash128::new(b"My Tuple App");
+        ctx.push_element(&t1).push_element(&t2).push_element(&t3).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("e60f202c89a2631eda8d4c588ca5fd07f39e5151998deccf973adb3804bb6e84")?,
+            "TupleHash128 sample 3 failed"
+        );
+        ctx.reset().push_element(&t1).push_element(&t2).push_element(&t3).finish_xof(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("900fe16cad098d28e74d632ed852f99daab7f7df4d99e775657885b4bf76d6f8")?,
+            "TupleHashXOF128 failed"
+        );
+
+        // moving the boundary between elements must change the digest
+        let mut other: Digest<32> = Digest::new();
+        ctx.reset().push_element(&t1).push_element(&t2).push_element(&t3).finish_into(&mut digest.0);
+        ctx.reset().push_element(&t1[..2]).push_element(&t1[2..]).push_element(&t2).push_element(&t3).finish_into(&mut other.0);
+        assert!(digest != other, "TupleHash ignored an element boundary");
+        assert!(ctx.reset().push_element(&t1).push_element(&t2).push_element(&t3).finish() == digest, "TupleHash::finish() failed");
+
+        let mut digest: Digest<64> = Digest::new();
+        TupleHash256::<64>::new(b"My Tuple App").push_element(&t1).push_element(&t2).push_element(&t3).finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "45000be63f9b6bfd89f54717670f69a9bc763591a4f05c50d68891a744bcc6e7\
+                     d6d5b5e82c018da999ed35b0bb49c9678e526abd8e85c13ed254021db9e790ce"
+                )?,
+            "TupleHash256 sample 6 failed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parallel_hash_vectors() -> Result<(), Box<dyn Error>> {
+        let data: Vec<u8> = [(0x00..0x08), (0x10..0x18), (0x20..0x28)].into_iter().flatten().collect();
+
+        let mut digest: Digest<32> = Digest::new();
+        ParallelHash128::<32>::new(8, b"")?.update(&data).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("ba8dc1d1d979331d3f813603c67f72609ab5e44b94a0b8f9af46514454a2b4f5")?,
+            "ParallelHash128 sample 1 failed"
+        );
+
+        let mut ctx: ParallelHash128<32> = ParallelHash128::new(8, b"Parallel Data")?;
+        ctx.update(&data).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("fc484dcb3f84dceedc353438151bee58157d6efed0445a81f165e495795b7206")?,
+            "ParallelHash128 sample 2 failed"
+        );
+
+        // feed the data in pieces that do not line up with the blocks, ending with a partial block
+        ctx.reset().update(&data[..3]).update(&data[3..13]).update(&data[13..20]).finish_into(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("def293f61b4a85fbb4dee209543f6b67c4f45445bbb25ad1ee141d9317d2cd2d")?,
+            "ParallelHash128 with a partial block failed"
+        );
+
+        ctx.reset().update(&data).finish_xof(&mut digest.0);
+        assert!(
+            digest == Digest::from_str("ea2a793140820f7a128b8eb70a9439f93257c6e6e79b4a540d291d6dae7098d7")?,
+            "ParallelHashXOF128 failed"
+        );
+
+        let mut digest: Digest<64> = Digest::new();
+        ParallelHash256::<64>::new(8, b"Parallel Data")?.update(&data).finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "cdf15289b54f6212b4bc270528b49526006dd9b54e2b6add1ef6900dda3963bb\
+                     33a72491f236969ca8afaea29c682d47a393c065b38e29fae651a2091c833110"
+                )?,
+            "ParallelHash256 sample 4 failed"
+        );
+
+        let big: Vec<u8> = (0..20000u32).map(|i| (i % 251) as u8).collect();
+        ParallelHash256::<64>::init().update(&big).finish_into(&mut digest.0);
+        assert!(
+            digest
+                == Digest::from_str(
+                    "3dd81c4a994dac78885eae724d74a0f6aaed8d8a37fb05110e9874bfe74140d0\
+                     bdc3558f32689b6997a4030855646e4971f806af3b42da10f0975f908b00eb20"
+                )?,
+            "ParallelHash256 with the default block size failed"
+        );
+
+        assert!(ParallelHash128::<32>::new(0, b"").is_err(), "ParallelHash accepted a zero block size");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+pub mod test {
+
+    use bc_hash::sha2::Midstate256;
+    use bc_hash::tagged::*;
+    use bc_hash::OneWayHasher;
+    use sha2::Digest;
+    use std::error::Error;
+
+    /// The reference calculation of SHA256(SHA256(tag) || SHA256(tag) || msg).
+    fn reference(tag: &str, msg: &[u8]) -> [u8; 32] {
+        let tag_hash = sha2::Sha256::digest(tag.as_bytes());
+        let mut ctx = sha2::Sha256::new();
+        ctx.update(tag_hash);
+        ctx.update(tag_hash);
+        ctx.update(msg);
+        ctx.finalize().into()
+    }
+
+    fn check<T: Tag>() -> Result<(), Box<dyn Error>> {
+        let midstate: Midstate256 = tag_midstate(T::NAME.as_bytes())?;
+        assert!(midstate == T::MIDSTATE, "The precomputed midstate of {} is wrong", T::NAME);
+
+        let msg: Vec<u8> = (0..100u8).collect();
+        let mut digest: [u8; 32] = [0; 32];
+        let mut ctx: TaggedHash<T> = TaggedHash::init();
+        for len in [0, 1, 32, 64, 100] {
+            ctx.reset().update(&msg[..len]).finish_into(&mut digest);
+            assert!(digest == reference(T::NAME, &msg[..len]), "The tagged hash with {} failed", T::NAME);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn standard_tags() -> Result<(), Box<dyn Error>> {
+        check::<Bip340Challenge>()?;
+        check::<Bip340Aux>()?;
+        check::<Bip340Nonce>()?;
+        check::<TapLeaf>()?;
+        check::<TapBranch>()?;
+        check::<TapTweak>()?;
+        check::<TapSighash>()?;
+        Ok(())
+    }
+
+    #[test]
+    fn custom_tag() -> Result<(), Box<dyn Error>> {
+        use bc_hash::sha2::Sha256;
+
+        let mut digest: [u8; 32] = [0; 32];
+        let mut ctx: Sha256 = Sha256::from_midstate(&tag_midstate(b"Example/custom")?)?;
+        ctx.update(b"hello world").finish_into(&mut digest);
+        assert!(digest == reference("Example/custom", b"hello world"), "A custom tagged hash failed");
+        Ok(())
+    }
+}